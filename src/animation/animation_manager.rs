@@ -233,17 +233,20 @@ fn handle_manager_changes<StateMachine: AnimationStateMachine>(
         (Entity, &AnimationManager<StateMachine>, Option<&Children>),
         Changed<AnimationManager<StateMachine>>,
     >,
+    old_bodies: Query<(), With<AnimationIndex<StateMachine>>>,
     ass: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut mats: ResMut<Assets<AnimationMaterial>>,
 ) {
     for (eid, manager, ochildren) in &managers {
+        // Only throw out our own bodies, other children (i.e. rig parts) belong to someone else
         if let Some(children) = ochildren {
             for child in children {
-                commands.entity(*child).insert(Dying::new(0.0));
+                if old_bodies.contains(*child) {
+                    commands.entity(*child).despawn_recursive();
+                }
             }
         }
-        commands.entity(eid).despawn_descendants();
         let mut new_progress_map = HashMap::new();
         let state_data = manager.get_state().to_state_data();
         for (ix, (body, overwrite)) in state_data.overwritten_bodies.into_iter().enumerate() {
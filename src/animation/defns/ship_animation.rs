@@ -3,23 +3,145 @@ use super::*;
 defn_animation!(
     AnimationShip,
     bodies: [
-        ship: {
-            path: "play/egg.png",
+        body: {
+            path: "ship/ship_body.png",
             size: (24, 24),
         },
+        fly: {
+            path: "play/fly.png",
+            size: (24, 24),
+            length: 3,
+            fps: 16.0,
+        },
+        damage: {
+            path: "play/fly_damage.png",
+            size: (24, 24),
+            length: 3,
+            fps: 16.0,
+        },
+        dying: {
+            path: "play/fly_damage.png",
+            size: (24, 24),
+            length: 3,
+            fps: 12.0,
+        },
         spotlight: {
             path: "play/spotlight.png",
             size: (48, 48),
             scale: (2.0, 2.0),
             render_layers: LightLayer::render_layers(),
         },
+        // Smaller light while resting on a planet
+        spotlight_dim: {
+            path: "play/spotlight.png",
+            size: (48, 48),
+            scale: (1.25, 1.25),
+            render_layers: LightLayer::render_layers(),
+        },
     ],
     states: [
         Cruise: {
             parts: [
-                ship,
+                body,
+                spotlight,
+            ],
+        },
+        Idle: {
+            parts: [
+                body,
+                spotlight_dim,
+            ],
+        },
+        Launch: {
+            parts: [
+                fly,
+                spotlight,
+            ],
+        },
+        Damage: {
+            parts: [
+                damage,
+                spotlight,
+            ],
+        },
+        Dying: {
+            parts: [
+                dying,
                 spotlight,
             ],
+            #[special]
+            next: HideThenDie(0.0),
+        },
+    ],
+);
+
+// The gun and tail are rig parts (see `rig.rs`) so they can turn on their own.
+// They're stowed whenever the ship isn't showing its body.
+defn_animation!(
+    AnimationShipGun,
+    bodies: [
+        gun: {
+            path: "ship/ship_gun.png",
+            size: (24, 24),
+        },
+    ],
+    states: [
+        Out: {
+            parts: [
+                gun,
+            ],
+        },
+        Stowed: {
+            parts: [],
+        },
+    ],
+);
+
+defn_animation!(
+    AnimationShipTail,
+    bodies: [
+        tail: {
+            path: "ship/ship_tail.png",
+            size: (24, 24),
+        },
+    ],
+    states: [
+        Out: {
+            parts: [
+                tail,
+            ],
+        },
+        Stowed: {
+            parts: [],
+        },
+    ],
+);
+
+defn_animation!(
+    AnimationShipExplosion,
+    bodies: [
+        core: {
+            path: "enemies/suicido/explosion_circle.png",
+            size: (64, 64),
+            length: 4,
+            fps: 10.0,
+        },
+        light: {
+            path: "enemies/suicido/explosion_circle.png",
+            size: (64, 64),
+            length: 4,
+            fps: 10.0,
+            render_layers: LightLayer::render_layers(),
+        },
+    ],
+    states: [
+        Boom: {
+            parts: [
+                core,
+                light,
+            ],
+            #[special]
+            next: HideThenDie(0.1),
         },
     ],
 );
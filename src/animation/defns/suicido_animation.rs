@@ -46,6 +46,14 @@ defn_animation!(
                 launch,
             ],
         },
+        // Just got shot (see `DamageFlash`)
+        Hurt: {
+            parts: [
+                charge: {
+                    override_color: tailwind::RED_400.into(),
+                },
+            ],
+        },
         Explode: {
             parts: [
                 explode,
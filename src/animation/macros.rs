@@ -48,7 +48,7 @@ macro_rules! defn_animation {
                                 }
                             )?
                             $(,)?
-                        )+
+                        )*
                     ],
                     $(
                         #[special]
@@ -122,7 +122,7 @@ macro_rules! defn_animation {
                 }
             }
 
-            #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Reflect)]
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Reflect, Serialize, Deserialize)]
             pub enum $name {
                 #[default]
                 $($state_id,)+
@@ -134,12 +134,24 @@ macro_rules! defn_animation {
                     match &self {
                         $(
                             Self::$state_id => {
+                                #[allow(unused_mut)]
                                 let mut overwritten_bodies = vec![];
 
                                 $(
                                     let part_id = Self::BodyType::$part_id;
                                     #[allow(unused, unused_mut)]
                                     let mut overwrite = AnimationBodyDataOverrides::default();
+                                    $(
+                                        $(
+                                            overwrite.override_offset = Some(Vec3::new($oox, $ooy, $ooz));
+                                        )?
+                                        $(
+                                            overwrite.override_scale = Some(Vec3::new($osx, $osy, $osz).truncate());
+                                        )?
+                                        $(
+                                            overwrite.override_color = Some($osc);
+                                        )?
+                                    )?
                                     overwritten_bodies.push((part_id, overwrite));
                                 )+
 
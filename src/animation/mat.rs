@@ -32,7 +32,6 @@ impl AnimationMaterial {
         repetitions: Vec2,
         color: Color,
     ) -> Self {
-        let srgba_thing = color.to_srgba();
         Self {
             texture,
             ix_length_flipx_flipy: Vec4::new(
@@ -42,18 +41,23 @@ impl AnimationMaterial {
                 Self::flip_to_mul(flip_y),
             ),
             xoff_yoff_xrep_yrep: Vec4::new(0.0, 0.0, repetitions.x, repetitions.y),
-            rgba: Vec4::new(
-                srgba_thing.red,
-                srgba_thing.green,
-                srgba_thing.blue,
-                srgba_thing.alpha,
-            ),
+            rgba: Self::color_to_rgba(color),
         }
     }
 
     pub(super) fn set_ix(&mut self, ix: u32) {
         self.ix_length_flipx_flipy[0] = ix as f32;
     }
+
+    fn color_to_rgba(color: Color) -> Vec4 {
+        let srgba_thing = color.to_srgba();
+        Vec4::new(
+            srgba_thing.red,
+            srgba_thing.green,
+            srgba_thing.blue,
+            srgba_thing.alpha,
+        )
+    }
 }
 
 impl Material2d for AnimationMaterial {
@@ -7,12 +7,14 @@ pub mod defns;
 pub mod macros;
 pub mod mat;
 pub(self) mod mesh;
+pub mod rig;
 pub mod texture_manager;
 
 pub use animation_manager::*;
 pub use defns::*;
 pub use macros::*;
 pub use mat::*;
+pub use rig::*;
 pub use texture_manager::*;
 
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -29,5 +31,7 @@ impl Plugin for AnimationPlugin {
 
         app.add_plugins(Material2dPlugin::<mat::AnimationMaterial>::default());
         app.add_plugins(defns::AnimationDefnsPlugin);
+
+        rig::register_rig(app);
     }
 }
@@ -0,0 +1,121 @@
+//! Sprites made out of parts that turn on their own (i.e. the ship's gun tracking where it's aimed).
+//!
+//! A part is just a child entity with a `RigPart` and its own animation manager. Every frame it turns
+//! (at most `turn_speed`) towards its target, which is a world angle that gameplay sets. Without a target it
+//! turns back to whatever its parent is doing. The part's transform is then placed so that its `pivot`
+//! sits on the parent's `anchor`.
+//!
+//! The parts' bodies are regular animation bodies, so mirages follow them (rotation included). Parts copy
+//! the `MirageAnimationManager` of their parent, so a rig with mirages has mirages of all its parts.
+//! NOTE: The parent's manager only respawns its own bodies, parts survive its state changes.
+
+use crate::prelude::*;
+
+/// Gameplay that sets part targets should run before this
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RigSet;
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct RigPart {
+    /// Where the pivot sits, in the parent's space
+    anchor: Vec2,
+    /// The point the part turns around, in the part's own (unturned) space
+    pivot: Vec2,
+    z: f32,
+    /// Radians per second. Infinite snaps straight to the target.
+    turn_speed: f32,
+    /// The world angle this is turning towards. `None` means follow the parent.
+    target: Option<f32>,
+    /// The current world angle (`None` until the first update, which snaps it)
+    angle: Option<f32>,
+}
+impl RigPart {
+    pub fn new(anchor: Vec2, pivot: Vec2) -> Self {
+        Self {
+            anchor,
+            pivot,
+            z: 0.0,
+            turn_speed: f32::INFINITY,
+            target: None,
+            angle: None,
+        }
+    }
+
+    impl_get_set_with!(anchor, Vec2);
+    impl_get_set_with!(pivot, Vec2);
+    impl_get_set_with!(z, f32);
+    impl_get_set_with!(turn_speed, f32);
+    impl_get_set_with!(target, Option<f32>);
+    impl_get!(angle, Option<f32>);
+}
+
+#[derive(Bundle)]
+pub struct RigPartBundle<StateMachine: AnimationStateMachine> {
+    name: Name,
+    part: RigPart,
+    spatial: SpatialBundle,
+    animation: AnimationManager<StateMachine>,
+}
+impl<StateMachine: AnimationStateMachine> RigPartBundle<StateMachine> {
+    pub fn new(name: &str, part: RigPart) -> Self {
+        Self {
+            name: Name::new(name.to_string()),
+            spatial: spat_tran!(part.anchor.x, part.anchor.y, part.z),
+            part,
+            animation: AnimationManager::new(),
+        }
+    }
+}
+
+fn update_rig_parts(
+    mut parts: Query<(&mut RigPart, &mut Transform, &Parent)>,
+    parents: Query<&GlobalTransform>,
+    bullet_time: Res<BulletTime>,
+) {
+    for (mut part, mut tran, parent) in &mut parts {
+        let Ok(parent_gtran) = parents.get(parent.get()) else {
+            continue;
+        };
+        let parent_angle = parent_gtran.pos_n_angle().1;
+        let goal = part.target.unwrap_or(parent_angle);
+        let angle = match part.angle {
+            Some(angle) => {
+                let max_turn = part.turn_speed * bullet_time.delta_seconds();
+                angle + shortest_rotation(angle, goal).clamp(-max_turn, max_turn)
+            }
+            None => goal,
+        };
+        part.angle = Some(angle);
+        let local_angle = angle - parent_angle;
+        tran.translation = (part.anchor - part.pivot.my_rotate(local_angle)).extend(part.z);
+        tran.rotation = Quat::from_rotation_z(local_angle);
+    }
+}
+
+/// Parts mirror wherever their parent mirrors
+fn sync_rig_mirages(
+    parts: Query<(Entity, &Parent, Option<&MirageAnimationManager>), With<RigPart>>,
+    mirages: Query<Ref<MirageAnimationManager>>,
+    mut commands: Commands,
+) {
+    for (eid, parent, part_mirage) in &parts {
+        let Ok(parent_mirage) = mirages.get(parent.get()) else {
+            continue;
+        };
+        if part_mirage.is_none() || parent_mirage.is_changed() {
+            commands.entity(eid).insert((*parent_mirage).clone());
+        }
+    }
+}
+
+pub(super) fn register_rig(app: &mut App) {
+    app.register_type::<RigPart>();
+
+    app.add_systems(
+        Update,
+        (update_rig_parts, sync_rig_mirages)
+            .in_set(RigSet)
+            .after(PhysicsSet)
+            .before(AnimationSet),
+    );
+}
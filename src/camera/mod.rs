@@ -16,7 +16,7 @@ pub struct DynamicCameraLeader;
 
 /// States that the dynamic camera can be in with respect to movement
 #[derive(Component, Debug, Clone, Reflect)]
-enum DynamicCameraMode {
+pub enum DynamicCameraMode {
     /// The camera is still and not moving
     Fixed,
     /// If there is one entity with a `DynamicCameraLeader` component, it will ensure that
@@ -0,0 +1,133 @@
+//! Conversations are defined in `.convo.ron` files under `assets/convos`.
+//! A file is a graph of nodes. Each node is (optionally) a line of dialogue, and says where to go next:
+//! another node, a set of choices, a branch on a variable, or the end of the conversation.
+//!
+//! Nodes can set variables (see `ConvoVars`) when they're entered, and lines can show them with `{name}`.
+//! A node without any text is passed through immediately, which is handy for setting variables and branching.
+
+use std::collections::BTreeMap;
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeakerDef {
+    /// What shows up above their lines
+    pub name: String,
+    /// Asset path of their portrait (if they have one)
+    #[serde(default)]
+    pub portrait: Option<String>,
+}
+
+/// Variables that haven't been set are 0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ConvoCondition {
+    AtLeast(String, i32),
+    Equals(String, i32),
+}
+impl ConvoCondition {
+    pub fn check(&self, vars: &ConvoVars) -> bool {
+        match self {
+            Self::AtLeast(var, val) => vars.get(var) >= *val,
+            Self::Equals(var, val) => vars.get(var) == *val,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvoChoice {
+    pub text: String,
+    pub goto: String,
+    /// If this isn't met the choice isn't shown
+    #[serde(default)]
+    pub requires: Option<ConvoCondition>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum ConvoNext {
+    #[default]
+    End,
+    Goto(String),
+    Choices(Vec<ConvoChoice>),
+    Branch {
+        cond: ConvoCondition,
+        then: String,
+        otherwise: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConvoNode {
+    /// Key into `ConvoDef::speakers`
+    #[serde(default)]
+    pub speaker: Option<String>,
+    #[serde(default)]
+    pub text: String,
+    /// Variables to set when this node is entered
+    #[serde(default)]
+    pub set: Vec<(String, i32)>,
+    #[serde(default)]
+    pub next: ConvoNext,
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct ConvoDef {
+    pub start: String,
+    #[serde(default)]
+    pub speakers: BTreeMap<String, SpeakerDef>,
+    pub nodes: BTreeMap<String, ConvoNode>,
+}
+
+#[derive(Debug)]
+pub enum ConvoDefLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+impl std::fmt::Display for ConvoDefLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read convo file: {err}"),
+            Self::Ron(err) => write!(f, "couldn't parse convo file: {err}"),
+        }
+    }
+}
+impl std::error::Error for ConvoDefLoaderError {}
+impl From<std::io::Error> for ConvoDefLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for ConvoDefLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+#[derive(Default)]
+struct ConvoDefLoader;
+impl AssetLoader for ConvoDefLoader {
+    type Asset = ConvoDef;
+    type Settings = ();
+    type Error = ConvoDefLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<ConvoDef>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["convo.ron"]
+    }
+}
+
+pub(super) fn register_convo_files(app: &mut App) {
+    app.init_asset::<ConvoDef>();
+    app.init_asset_loader::<ConvoDefLoader>();
+}
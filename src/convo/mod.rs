@@ -0,0 +1,536 @@
+//! Conversations. Send `StartConvo` with the path of a `.convo.ron` file (see `convo_file.rs`) and it'll
+//! show up in a box at the bottom of the screen, one line at a time. `ConvoFinished` is sent when it closes.
+//! Only one conversation can be open at a time.
+//!
+//! Text is typed out, and laid out on the ideal pixel grid (positions and font sizes are whole ideal
+//! pixels) so it sits nicely with the rest of the art. `ConvoGoNext` finishes typing, then advances.
+//! Choices are picked with `NonGameInput` (up/down/continue, or pressing one).
+//!
+//! Gameplay slows down while a conversation is open.
+
+use bevy::asset::LoadState;
+use bevy::sprite::Anchor;
+use bevy::text::Text2dBounds;
+
+use crate::prelude::*;
+
+pub mod convo_file;
+
+pub use convo_file::*;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConvoSet;
+
+/// Open the conversation at this asset path, i.e. "convos/intro.convo.ron"
+#[derive(Event, Debug, Clone)]
+pub struct StartConvo {
+    pub path: String,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct ConvoFinished;
+
+#[derive(Resource, Reflect)]
+struct ConvoConstants {
+    chars_per_second: f32,
+    /// What `BulletTime` is set to while a conversation is open
+    gameplay_time_factor: f32,
+}
+impl Default for ConvoConstants {
+    fn default() -> Self {
+        Self {
+            chars_per_second: 40.0,
+            gameplay_time_factor: 0.0,
+        }
+    }
+}
+
+// Layout, in ideal pixels
+const BOX_SIZE: Vec2 = Vec2::new(300.0, 48.0);
+const BOX_Y: f32 = -62.0;
+const PORTRAIT_SIZE: f32 = 40.0;
+const PADDING: f32 = 4.0;
+const FONT_SIZE: f32 = 8.0;
+const CHOICE_HEIGHT: f32 = 12.0;
+const CHOICE_WIDTH: f32 = 200.0;
+
+const BOX_COLOR: Srgba = tailwind::GRAY_900;
+const NAME_COLOR: Srgba = tailwind::SKY_400;
+const CHOICE_COLOR: Srgba = tailwind::GRAY_400;
+const CHOICE_FOCUSED_COLOR: Srgba = tailwind::SKY_300;
+
+/// Converts ideal pixels to menu pixels, snapping to the ideal pixel grid
+fn menu_px(ideal: f32) -> f32 {
+    ideal.round() * MENU_GROWTH_f32
+}
+
+fn text_left() -> f32 {
+    -BOX_SIZE.x / 2.0 + PADDING * 2.0 + PORTRAIT_SIZE
+}
+
+fn choice_y(ix: usize, num_choices: usize) -> f32 {
+    BOX_Y + BOX_SIZE.y / 2.0 + CHOICE_HEIGHT / 2.0 + (num_choices - 1 - ix) as f32 * CHOICE_HEIGHT
+}
+
+/// Variables that conversations can set and branch on. They stick around between conversations.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct ConvoVars {
+    vars: HashMap<String, i32>,
+}
+impl ConvoVars {
+    pub fn get(&self, var: &str) -> i32 {
+        self.vars.get(var).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, var: &str, val: i32) {
+        self.vars.insert(var.to_string(), val);
+    }
+
+    /// Replaces every `{var}` in the text with its value
+    fn fill_in(&self, text: &str) -> String {
+        let mut result = String::new();
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}') else {
+                break;
+            };
+            result.push_str(&rest[..open]);
+            result.push_str(&self.get(&rest[open + 1..open + close]).to_string());
+            rest = &rest[open + close + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+/// The conversation that's open (if any)
+#[derive(Resource, Debug, Default)]
+pub struct ActiveConvo {
+    handle: Option<Handle<ConvoDef>>,
+    /// None while the file is still loading
+    node: Option<String>,
+    /// The line being shown, with variables filled in
+    line: String,
+    /// How much of the line has been typed out
+    typed: f32,
+    focus: usize,
+    /// What the time factor was before the conversation started
+    restore_time_factor: f32,
+}
+impl ActiveConvo {
+    /// True from when a conversation is started until it finishes (including while it loads)
+    pub fn is_busy(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    fn line_len(&self) -> usize {
+        self.line.chars().count()
+    }
+
+    fn is_typed(&self) -> bool {
+        self.typed as usize >= self.line_len()
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct ConvoBox;
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct ConvoBody;
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct ConvoChoiceText {
+    ix: usize,
+}
+
+fn spawn_convo_box(
+    def: &ConvoDef,
+    node: &ConvoNode,
+    choices: &[&ConvoChoice],
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    convo_root: &ConvoRoot,
+) {
+    let speaker = node
+        .speaker
+        .as_ref()
+        .and_then(|speaker| def.speakers.get(speaker));
+    let style = TextStyle {
+        font_size: menu_px(FONT_SIZE),
+        color: Color::WHITE,
+        ..default()
+    };
+    commands
+        .spawn((
+            Name::new("convo_box"),
+            ConvoBox,
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, ZIX_CONVO)),
+        ))
+        .set_parent(convo_root.eid())
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("background"),
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: BOX_COLOR.into(),
+                        custom_size: Some(BOX_SIZE * MENU_GROWTH_f32),
+                        ..default()
+                    },
+                    transform: Transform::from_xyz(0.0, menu_px(BOX_Y), 0.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+            if let Some(portrait) = speaker.and_then(|speaker| speaker.portrait.as_ref()) {
+                parent.spawn((
+                    Name::new("portrait"),
+                    SpriteBundle {
+                        texture: asset_server.load(portrait.clone()),
+                        sprite: Sprite {
+                            custom_size: Some(Vec2::ONE * menu_px(PORTRAIT_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(
+                            menu_px(-BOX_SIZE.x / 2.0 + PADDING + PORTRAIT_SIZE / 2.0),
+                            menu_px(BOX_Y),
+                            1.0,
+                        ),
+                        ..default()
+                    },
+                    MenuLayer::render_layers(),
+                ));
+            }
+            let mut top = BOX_Y + BOX_SIZE.y / 2.0 - PADDING;
+            if let Some(speaker) = speaker {
+                parent.spawn((
+                    Name::new("name"),
+                    Text2dBundle {
+                        text: Text::from_section(
+                            speaker.name.clone(),
+                            TextStyle {
+                                color: NAME_COLOR.into(),
+                                ..style.clone()
+                            },
+                        ),
+                        text_anchor: Anchor::TopLeft,
+                        transform: Transform::from_xyz(menu_px(text_left()), menu_px(top), 1.0),
+                        ..default()
+                    },
+                    MenuLayer::render_layers(),
+                ));
+                top -= FONT_SIZE + PADDING;
+            }
+            parent.spawn((
+                Name::new("body"),
+                ConvoBody,
+                Text2dBundle {
+                    text: Text::from_section("", style.clone()).with_justify(JustifyText::Left),
+                    text_anchor: Anchor::TopLeft,
+                    text_2d_bounds: Text2dBounds {
+                        size: Vec2::new(
+                            menu_px(BOX_SIZE.x / 2.0 - PADDING - text_left()),
+                            menu_px(BOX_SIZE.y),
+                        ),
+                    },
+                    transform: Transform::from_xyz(menu_px(text_left()), menu_px(top), 1.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+            for (ix, choice) in choices.iter().enumerate() {
+                parent.spawn((
+                    Name::new(format!("choice_{ix}")),
+                    ConvoChoiceText { ix },
+                    Text2dBundle {
+                        text: Text::from_section(choice.text.clone(), style.clone()),
+                        text_anchor: Anchor::CenterLeft,
+                        transform: Transform::from_xyz(
+                            menu_px(text_left()),
+                            menu_px(choice_y(ix, choices.len())),
+                            1.0,
+                        ),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    MenuLayer::render_layers(),
+                ));
+            }
+        });
+}
+
+/// The choices in this node that can actually be picked
+fn available_choices<'a>(node: &'a ConvoNode, vars: &ConvoVars) -> Vec<&'a ConvoChoice> {
+    match &node.next {
+        ConvoNext::Choices(choices) => choices
+            .iter()
+            .filter(|choice| {
+                choice
+                    .requires
+                    .as_ref()
+                    .map(|cond| cond.check(vars))
+                    .unwrap_or(true)
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Where to go after this node (when it isn't a choice). `None` means the conversation is over.
+fn follow(next: &ConvoNext, vars: &ConvoVars) -> Option<String> {
+    match next {
+        ConvoNext::End | ConvoNext::Choices(_) => None,
+        ConvoNext::Goto(key) => Some(key.clone()),
+        ConvoNext::Branch {
+            cond,
+            then,
+            otherwise,
+        } => Some(if cond.check(vars) {
+            then.clone()
+        } else {
+            otherwise.clone()
+        }),
+    }
+}
+
+fn close_convo(
+    active: &mut ActiveConvo,
+    boxes: &Query<Entity, With<ConvoBox>>,
+    bullet_time: &mut BulletTime,
+    finished: &mut EventWriter<ConvoFinished>,
+    commands: &mut Commands,
+) {
+    for eid in boxes {
+        commands.entity(eid).despawn_recursive();
+    }
+    bullet_time.set_time_factor(active.restore_time_factor);
+    *active = default();
+    finished.send(ConvoFinished);
+}
+
+fn start_convos(
+    mut starts: EventReader<StartConvo>,
+    mut transitions: EventReader<TransitionEvent>,
+    mut active: ResMut<ActiveConvo>,
+    boxes: Query<Entity, With<ConvoBox>>,
+    constants: Res<ConvoConstants>,
+    asset_server: Res<AssetServer>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut finished: EventWriter<ConvoFinished>,
+    mut commands: Commands,
+) {
+    // Conversations don't survive leaving whatever started them
+    let leaving = transitions
+        .read()
+        .any(|event| matches!(event, TransitionEvent::StartedLeaving { .. }));
+    if leaving && active.is_busy() {
+        close_convo(
+            &mut active,
+            &boxes,
+            &mut bullet_time,
+            &mut finished,
+            &mut commands,
+        );
+    }
+    for start in starts.read() {
+        if active.is_busy() {
+            warn!("Ignoring convo {}, one is already open", start.path);
+            continue;
+        }
+        *active = ActiveConvo {
+            handle: Some(asset_server.load(start.path.clone())),
+            restore_time_factor: bullet_time.get_time_factor(),
+            ..default()
+        };
+        bullet_time.set_time_factor(constants.gameplay_time_factor);
+    }
+}
+
+fn update_convo(
+    mut active: ResMut<ActiveConvo>,
+    mut vars: ResMut<ConvoVars>,
+    defs: Res<Assets<ConvoDef>>,
+    mut convo_input: EventReader<ConvoGoNext>,
+    mut non_game_input: EventReader<NonGameInput>,
+    boxes: Query<Entity, With<ConvoBox>>,
+    mut bodies: Query<&mut Text, (With<ConvoBody>, Without<ConvoChoiceText>)>,
+    mut choice_texts: Query<(&ConvoChoiceText, &mut Text, &mut Visibility), Without<ConvoBody>>,
+    constants: Res<ConvoConstants>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut finished: EventWriter<ConvoFinished>,
+    convo_root: Res<ConvoRoot>,
+    mut commands: Commands,
+) {
+    let went_next = convo_input.read().count() > 0;
+    let non_game_input: Vec<NonGameInput> = non_game_input.read().cloned().collect();
+    if let Some(handle) = active.handle.clone() {
+        // Otherwise whoever is waiting on the conversation (i.e. cutscenes) would wait forever
+        if matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) {
+            warn!("Couldn't load convo {:?}", handle.path());
+            close_convo(
+                &mut active,
+                &boxes,
+                &mut bullet_time,
+                &mut finished,
+                &mut commands,
+            );
+            return;
+        }
+    }
+    let Some(def) = active.handle.as_ref().and_then(|handle| defs.get(handle)) else {
+        return;
+    };
+
+    // Figure out if we need to move to a new node
+    let mut goto = None;
+    let mut close = false;
+    let current = active.node.clone();
+    match current.as_ref().and_then(|key| def.nodes.get(key)) {
+        None => {
+            if current.is_none() {
+                // Just finished loading
+                goto = Some(def.start.clone());
+            } else {
+                warn!("Convo node {current:?} doesn't exist");
+                close = true;
+            }
+        }
+        Some(node) => {
+            let choices = available_choices(node, &vars);
+            let mut picked = None;
+            for event in &non_game_input {
+                match event {
+                    NonGameInput::Up if !choices.is_empty() => {
+                        active.focus = (active.focus + choices.len() - 1) % choices.len();
+                    }
+                    NonGameInput::Down if !choices.is_empty() => {
+                        active.focus = (active.focus + 1) % choices.len();
+                    }
+                    NonGameInput::Continue if active.is_typed() && !choices.is_empty() => {
+                        picked = Some(active.focus);
+                    }
+                    NonGameInput::Press(screen_pos) if active.is_typed() => {
+                        let hit = (0..choices.len()).find(|ix| {
+                            let y = choice_y(*ix, choices.len());
+                            screen_pos.x >= text_left()
+                                && screen_pos.x <= text_left() + CHOICE_WIDTH
+                                && (screen_pos.y - y).abs() <= CHOICE_HEIGHT / 2.0
+                        });
+                        picked = hit.or(picked);
+                    }
+                    _ => (),
+                }
+            }
+            if went_next && picked.is_none() {
+                if !active.is_typed() {
+                    active.typed = active.line_len() as f32;
+                } else if choices.is_empty() {
+                    goto = follow(&node.next, &vars);
+                    close = goto.is_none();
+                }
+                // NOTE: With choices up, only `Continue` or pressing a choice picks one. Moving the focus
+                // and clicking anywhere else send `ConvoGoNext` too.
+            }
+            if let Some(choice) = picked.and_then(|ix| choices.get(ix)) {
+                goto = Some(choice.goto.clone());
+            }
+        }
+    }
+
+    // Enter the new node, passing through any that don't have text
+    let mut passed_through = 0;
+    while let Some(key) = goto.take() {
+        let Some(node) = def.nodes.get(&key) else {
+            warn!("Convo node {key} doesn't exist");
+            close = true;
+            break;
+        };
+        for (var, val) in &node.set {
+            vars.set(var, *val);
+        }
+        let choices = available_choices(node, &vars);
+        passed_through += 1;
+        if node.text.is_empty() && choices.is_empty() && passed_through < 100 {
+            goto = follow(&node.next, &vars);
+            close = goto.is_none();
+            continue;
+        }
+        for eid in &boxes {
+            commands.entity(eid).despawn_recursive();
+        }
+        spawn_convo_box(
+            def,
+            node,
+            &choices,
+            &asset_server,
+            &mut commands,
+            &convo_root,
+        );
+        active.line = vars.fill_in(&node.text);
+        active.typed = 0.0;
+        active.focus = 0;
+        active.node = Some(key);
+        close = false;
+    }
+    if close {
+        close_convo(
+            &mut active,
+            &boxes,
+            &mut bullet_time,
+            &mut finished,
+            &mut commands,
+        );
+        return;
+    }
+
+    // Type out the line, and show the choices once it's done
+    active.typed += constants.chars_per_second * time.delta_seconds();
+    let typed: String = active.line.chars().take(active.typed as usize).collect();
+    for mut text in &mut bodies {
+        if text.sections[0].value != typed {
+            text.sections[0].value = typed.clone();
+        }
+    }
+    let is_typed = active.is_typed();
+    for (choice, mut text, mut visibility) in &mut choice_texts {
+        *visibility = if is_typed {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        text.sections[0].style.color = if choice.ix == active.focus {
+            CHOICE_FOCUSED_COLOR.into()
+        } else {
+            CHOICE_COLOR.into()
+        };
+    }
+}
+
+pub(super) struct ConvoPlugin;
+impl Plugin for ConvoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ConvoVars>();
+        app.register_type::<ConvoBox>();
+        app.register_type::<ConvoBody>();
+        app.register_type::<ConvoChoiceText>();
+        app.add_event::<StartConvo>();
+        app.add_event::<ConvoFinished>();
+        app.insert_resource(ActiveConvo::default());
+        app.insert_resource(ConvoVars::default());
+        app.insert_resource(ConvoConstants::default());
+        debug_resource!(app, ConvoConstants);
+        debug_resource!(app, ConvoVars);
+
+        convo_file::register_convo_files(app);
+
+        app.add_systems(
+            Update,
+            (start_convos, update_convo)
+                .chain()
+                .in_set(ConvoSet)
+                .after(InputSet)
+                .run_if(in_state(PauseState::Unpaused)),
+        );
+    }
+}
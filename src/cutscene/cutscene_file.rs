@@ -0,0 +1,161 @@
+//! Cutscenes are defined in `.cutscene.ron` files under `assets/cutscenes`.
+//! A file is a list of steps that play one after another, and says where to go once they're done.
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+
+use crate::prelude::*;
+
+/// Things that can show up in a cutscene. These are just the visuals, no physics.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ActorKind {
+    Ship,
+    Suicido,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum ActorAnimation {
+    Ship(AnimationShip),
+    Suicido(AnimationSuicidoBody),
+    Hidden(bool),
+    FlipX(bool),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CutsceneStep {
+    /// NOTE: The actor only exists starting the step after this one, so don't spawn and use it in
+    /// the same `Parallel`
+    Spawn {
+        actor: String,
+        kind: ActorKind,
+        pos: Vec2,
+    },
+    Despawn {
+        actor: String,
+    },
+    /// Move an actor from wherever it is through each of the points in `path`
+    Move {
+        actor: String,
+        path: Vec<Vec2>,
+        time: f32,
+        spleen: Spleen,
+    },
+    /// Move the camera from wherever it is through each of the points in `path`
+    Camera {
+        path: Vec<Vec2>,
+        time: f32,
+        spleen: Spleen,
+    },
+    Animate {
+        actor: String,
+        animation: ActorAnimation,
+    },
+    /// Open a conversation (see `convo`) and wait for it to finish
+    Convo(String),
+    /// Show a line of text and wait for `ConvoGoNext`
+    Say {
+        speaker: String,
+        text: String,
+    },
+    Wait(f32),
+    /// Starts all of these steps at once and finishes when they're all finished
+    Parallel(Vec<CutsceneStep>),
+}
+impl CutsceneStep {
+    pub(super) fn is_done(&self, time: f32, went_next: bool, convo_busy: bool) -> bool {
+        match self {
+            Self::Spawn { .. } | Self::Despawn { .. } | Self::Animate { .. } => true,
+            Self::Move { time: length, .. } | Self::Camera { time: length, .. } => time >= *length,
+            // The conversation only opens once `ConvoSet` sees the request (next frame)
+            Self::Convo(_) => time > 0.0 && !convo_busy,
+            Self::Say { .. } => went_next,
+            Self::Wait(length) => time >= *length,
+            Self::Parallel(steps) => steps
+                .iter()
+                .all(|step| step.is_done(time, went_next, convo_busy)),
+        }
+    }
+}
+
+/// Where to go once a cutscene is over
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum CutsceneThen {
+    Menu(MenuState),
+    Cutscene(CutsceneState),
+    Tutorial(TutorialState),
+    /// The start of an encounter in whatever room is active
+    Encounter {
+        kind: EncounterKind,
+        difficulty: u32,
+    },
+}
+impl ToMetaState for CutsceneThen {
+    fn to_meta_state(&self) -> MetaState {
+        match self {
+            Self::Menu(menu_state) => menu_state.to_meta_state(),
+            Self::Cutscene(cutscene_state) => cutscene_state.to_meta_state(),
+            Self::Tutorial(tutorial_state) => tutorial_state.to_meta_state(),
+            Self::Encounter { kind, difficulty } => {
+                RoomState::xth_encounter(*kind, *difficulty).to_meta_state()
+            }
+        }
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct CutsceneScript {
+    pub steps: Vec<CutsceneStep>,
+    pub then: CutsceneThen,
+}
+
+#[derive(Debug)]
+pub enum CutsceneScriptLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+impl std::fmt::Display for CutsceneScriptLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read cutscene file: {err}"),
+            Self::Ron(err) => write!(f, "couldn't parse cutscene file: {err}"),
+        }
+    }
+}
+impl std::error::Error for CutsceneScriptLoaderError {}
+impl From<std::io::Error> for CutsceneScriptLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for CutsceneScriptLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+#[derive(Default)]
+struct CutsceneScriptLoader;
+impl AssetLoader for CutsceneScriptLoader {
+    type Asset = CutsceneScript;
+    type Settings = ();
+    type Error = CutsceneScriptLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<CutsceneScript>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cutscene.ron"]
+    }
+}
+
+pub(super) fn register_cutscene_files(app: &mut App) {
+    app.init_asset::<CutsceneScript>();
+    app.init_asset_loader::<CutsceneScriptLoader>();
+}
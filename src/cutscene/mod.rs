@@ -0,0 +1,415 @@
+//! Cutscenes are scripts (see `cutscene_file.rs`): a list of steps that play one after another.
+//! Steps can spawn actors, move them (or the camera) along paths, change their animations, and show
+//! lines of text that wait for `ConvoGoNext`. Everything a cutscene spawns lives under `CutsceneRoot`,
+//! so it's cleaned up by the transition out.
+//!
+//! `NonGameInput::Continue` (Enter, or a two-finger tap) skips the rest of the cutscene, unless a conversation
+//! is open (Enter picks choices there). Either way, once it's over we transition to whatever the script says
+//! comes next (usually gameplay).
+
+use bevy::asset::LoadState;
+use bevy::ecs::system::SystemParam;
+use bevy::text::Text2dBounds;
+
+use crate::prelude::*;
+
+pub mod cutscene_file;
+pub mod scripts;
+
+pub use cutscene_file::*;
+pub use scripts::*;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CutsceneSet;
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct CutsceneActor {
+    name: String,
+}
+
+#[derive(Bundle)]
+struct CutsceneActorBundle {
+    name: Name,
+    actor: CutsceneActor,
+    spatial: SpatialBundle,
+}
+impl CutsceneActorBundle {
+    fn new(name: &str, pos: Vec2, zix: f32) -> Self {
+        Self {
+            name: Name::new(format!("actor_{name}")),
+            actor: CutsceneActor {
+                name: name.to_string(),
+            },
+            spatial: SpatialBundle::from_transform(Transform::from_translation(pos.extend(zix))),
+        }
+    }
+}
+
+/// Moves whatever it's on along a path over time
+#[derive(Component, Debug, Clone, Reflect)]
+struct CutscenePath {
+    points: Vec<Vec2>,
+    time: f32,
+    length: f32,
+    spleen: Spleen,
+}
+impl CutscenePath {
+    fn new(from: Vec2, path: &[Vec2], length: f32, spleen: Spleen) -> Self {
+        let mut points = vec![from];
+        points.extend_from_slice(path);
+        Self {
+            points,
+            time: 0.0,
+            length,
+            spleen,
+        }
+    }
+
+    /// Where on the path we are at fraction `frac` of the total distance
+    fn sample(&self, frac: f32) -> Vec2 {
+        let total: f32 = self.points.windows(2).map(|w| w[0].distance(w[1])).sum();
+        let mut left = total * frac;
+        for w in self.points.windows(2) {
+            let seg = w[0].distance(w[1]);
+            if left <= seg && seg > 0.0 {
+                return w[0].lerp(w[1], left / seg);
+            }
+            left -= seg;
+        }
+        *self.points.last().unwrap()
+    }
+}
+
+/// The text box for `CutsceneStep::Say`
+#[derive(Component, Debug, Clone, Reflect)]
+struct CutsceneSpeech;
+
+#[derive(Bundle)]
+struct CutsceneSpeechBundle {
+    name: Name,
+    speech: CutsceneSpeech,
+    text: Text2dBundle,
+    render_layers: RenderLayers,
+}
+impl CutsceneSpeechBundle {
+    fn new(speaker: &str, text: &str) -> Self {
+        let style = TextStyle {
+            font_size: 36.0,
+            color: Color::WHITE,
+            ..default()
+        };
+        Self {
+            name: Name::new("cutscene_speech"),
+            speech: CutsceneSpeech,
+            text: Text2dBundle {
+                text: Text::from_sections([
+                    TextSection::new(
+                        format!("{speaker}\n"),
+                        TextStyle {
+                            color: tailwind::SKY_400.into(),
+                            ..style.clone()
+                        },
+                    ),
+                    TextSection::new(text, style),
+                ]),
+                text_2d_bounds: Text2dBounds {
+                    size: Vec2::new(MENU_VEC_f32.x * 0.8, MENU_VEC_f32.y * 0.3),
+                },
+                transform: Transform::from_xyz(0.0, -MENU_VEC_f32.y * 0.35, ZIX_MENU),
+                ..default()
+            },
+            render_layers: MenuLayer::render_layers(),
+        }
+    }
+}
+
+/// Where we are in the cutscene that's playing (if any)
+#[derive(Resource, Debug, Default)]
+struct CutscenePlayer {
+    /// The script to play, until it's loaded (and copied into `script`)
+    loading: Option<Handle<CutsceneScript>>,
+    script: Option<CutsceneScript>,
+    ix: usize,
+    time: f32,
+    started: bool,
+    went_next: bool,
+    /// Whether a convo was open last frame. The Enter that closes a convo shouldn't also skip.
+    convo_was_busy: bool,
+}
+
+#[derive(SystemParam)]
+struct CutsceneCtx<'w, 's> {
+    commands: Commands<'w, 's>,
+    actors: Query<'w, 's, (Entity, &'static CutsceneActor, &'static Transform)>,
+    camera_q: Query<'w, 's, (Entity, &'static Transform), With<DynamicCameraMarker>>,
+    ship_anims: Query<'w, 's, &'static mut AnimationManager<AnimationShip>>,
+    suicido_anims: Query<'w, 's, &'static mut AnimationManager<AnimationSuicidoBody>>,
+    speech_q: Query<'w, 's, Entity, With<CutsceneSpeech>>,
+    convos: EventWriter<'w, StartConvo>,
+    cutscene_root: Res<'w, CutsceneRoot>,
+    convo_root: Res<'w, ConvoRoot>,
+}
+impl<'w, 's> CutsceneCtx<'w, 's> {
+    fn find_actor(&self, name: &str) -> Option<(Entity, Vec2)> {
+        let found = self
+            .actors
+            .iter()
+            .find(|(_, actor, _)| actor.name == name)
+            .map(|(eid, _, tran)| (eid, tran.translation.truncate()));
+        if found.is_none() {
+            warn!("Cutscene actor {name} doesn't exist");
+        }
+        found
+    }
+
+    fn begin(&mut self, step: &CutsceneStep) {
+        match step {
+            CutsceneStep::Spawn { actor, kind, pos } => {
+                let mut ent_comm = self.commands.spawn(CutsceneActorBundle::new(
+                    actor,
+                    *pos,
+                    match kind {
+                        ActorKind::Ship => ZIX_SHIP,
+                        ActorKind::Suicido => ZIX_ENEMY,
+                    },
+                ));
+                match kind {
+                    ActorKind::Ship => {
+                        ent_comm.insert(AnimationManager::<AnimationShip>::new());
+                    }
+                    ActorKind::Suicido => {
+                        ent_comm.insert(AnimationManager::<AnimationSuicidoBody>::new());
+                    }
+                }
+                ent_comm.set_parent(self.cutscene_root.eid());
+            }
+            CutsceneStep::Despawn { actor } => {
+                if let Some((eid, _)) = self.find_actor(actor) {
+                    self.commands.entity(eid).despawn_recursive();
+                }
+            }
+            CutsceneStep::Move {
+                actor,
+                path,
+                time,
+                spleen,
+            } => {
+                if let Some((eid, pos)) = self.find_actor(actor) {
+                    self.commands
+                        .entity(eid)
+                        .insert(CutscenePath::new(pos, path, *time, *spleen));
+                }
+            }
+            CutsceneStep::Camera { path, time, spleen } => {
+                let (eid, tran) = self.camera_q.single();
+                let pos = tran.translation.truncate();
+                self.commands
+                    .entity(eid)
+                    .insert(CutscenePath::new(pos, path, *time, *spleen));
+            }
+            CutsceneStep::Animate { actor, animation } => {
+                let Some((eid, _)) = self.find_actor(actor) else {
+                    return;
+                };
+                match animation {
+                    ActorAnimation::Ship(state) => {
+                        if let Ok(mut anim) = self.ship_anims.get_mut(eid) {
+                            anim.set_state(*state);
+                        }
+                    }
+                    ActorAnimation::Suicido(state) => {
+                        if let Ok(mut anim) = self.suicido_anims.get_mut(eid) {
+                            anim.set_state(*state);
+                        }
+                    }
+                    ActorAnimation::Hidden(hidden) => {
+                        if let Ok(mut anim) = self.ship_anims.get_mut(eid) {
+                            anim.set_hidden(*hidden);
+                        }
+                        if let Ok(mut anim) = self.suicido_anims.get_mut(eid) {
+                            anim.set_hidden(*hidden);
+                        }
+                    }
+                    ActorAnimation::FlipX(flip_x) => {
+                        if let Ok(mut anim) = self.ship_anims.get_mut(eid) {
+                            anim.set_flip_x(*flip_x);
+                        }
+                        if let Ok(mut anim) = self.suicido_anims.get_mut(eid) {
+                            anim.set_flip_x(*flip_x);
+                        }
+                    }
+                }
+            }
+            CutsceneStep::Convo(path) => {
+                self.convos.send(StartConvo {
+                    path: path.to_string(),
+                });
+            }
+            CutsceneStep::Say { speaker, text } => {
+                self.commands
+                    .spawn(CutsceneSpeechBundle::new(speaker, text))
+                    .set_parent(self.convo_root.eid());
+            }
+            CutsceneStep::Wait(_) => {}
+            CutsceneStep::Parallel(steps) => {
+                for step in steps {
+                    self.begin(step);
+                }
+            }
+        }
+    }
+
+    fn end(&mut self) {
+        for eid in &self.speech_q {
+            self.commands.entity(eid).despawn_recursive();
+        }
+    }
+}
+
+fn start_cutscene(
+    meta_state: Res<State<MetaState>>,
+    scripts: Res<CutsceneScripts>,
+    mut player: ResMut<CutscenePlayer>,
+    mut camera_q: Query<(&mut DynamicCameraMode, &mut Transform), With<DynamicCameraMarker>>,
+) {
+    let Some(cutscene_state) = meta_state.get_cutscene_state() else {
+        return;
+    };
+    *player = CutscenePlayer {
+        loading: scripts.get_handle(cutscene_state),
+        ..default()
+    };
+    // Cutscenes drive the camera themselves
+    let (mut mode, mut tran) = camera_q.single_mut();
+    *mode = DynamicCameraMode::Fixed;
+    tran.translation = Vec3::ZERO;
+}
+
+/// Copies the script out of its asset once it's loaded. If it can't be loaded, there's nothing to play,
+/// so it goes back to the title.
+fn load_cutscene(
+    mut player: ResMut<CutscenePlayer>,
+    scripts: Res<Assets<CutsceneScript>>,
+    asset_server: Res<AssetServer>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    let Some(handle) = player.loading.clone() else {
+        return;
+    };
+    if let Some(script) = scripts.get(&handle) {
+        player.script = Some(script.clone());
+        player.loading = None;
+    } else if matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) {
+        warn!("Couldn't load cutscene {:?}", handle.path());
+        player.loading = None;
+        transitions.send(RequestTransition {
+            to: MenuState::Title.to_meta_state(),
+        });
+    }
+}
+
+fn update_cutscene(
+    mut player: ResMut<CutscenePlayer>,
+    mut convo_input: EventReader<ConvoGoNext>,
+    mut non_game_input: EventReader<NonGameInput>,
+    mut transitions: EventWriter<RequestTransition>,
+    time: Res<Time>,
+    active_convo: Res<ActiveConvo>,
+    mut ctx: CutsceneCtx,
+) {
+    let went_next = convo_input.read().count() > 0;
+    let player = &mut *player;
+    // Enter also confirms convo choices, so it can't skip while a convo is open (or just closed)
+    let convo_busy = active_convo.is_busy();
+    let skipped = non_game_input
+        .read()
+        .any(|event| matches!(event, NonGameInput::Continue))
+        && !convo_busy
+        && !player.convo_was_busy;
+    player.convo_was_busy = convo_busy;
+    let Some(script) = &player.script else {
+        return;
+    };
+    let Some(step) = script.steps.get(player.ix).filter(|_| !skipped) else {
+        ctx.end();
+        transitions.send(RequestTransition {
+            to: script.then.to_meta_state(),
+        });
+        player.script = None;
+        return;
+    };
+    if player.started {
+        player.time += time.delta_seconds();
+        player.went_next = player.went_next || went_next;
+    } else {
+        ctx.begin(step);
+        player.started = true;
+        player.time = 0.0;
+        player.went_next = false;
+    }
+    if step.is_done(player.time, player.went_next, convo_busy) {
+        ctx.end();
+        player.ix += 1;
+        player.started = false;
+    }
+}
+
+fn update_cutscene_paths(
+    mut path_q: Query<(Entity, &mut CutscenePath, &mut Transform)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (eid, mut path, mut tran) in &mut path_q {
+        path.time += time.delta_seconds();
+        let frac = (path.time / path.length).min(1.0);
+        let pos = path.sample(path.spleen.interp(frac));
+        tran.translation.x = pos.x;
+        tran.translation.y = pos.y;
+        if frac >= 1.0 {
+            commands.entity(eid).remove::<CutscenePath>();
+        }
+    }
+}
+
+fn stop_cutscene(
+    mut player: ResMut<CutscenePlayer>,
+    mut camera_q: Query<(Entity, &mut DynamicCameraMode), With<DynamicCameraMarker>>,
+    speech_q: Query<Entity, With<CutsceneSpeech>>,
+    mut commands: Commands,
+) {
+    player.loading = None;
+    player.script = None;
+    let (eid, mut mode) = camera_q.single_mut();
+    *mode = DynamicCameraMode::Follow;
+    commands.entity(eid).remove::<CutscenePath>();
+    for eid in &speech_q {
+        commands.entity(eid).despawn_recursive();
+    }
+}
+
+pub(super) struct CutscenePlugin;
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CutsceneActor>();
+        app.register_type::<CutscenePath>();
+        app.register_type::<CutsceneSpeech>();
+        app.insert_resource(CutscenePlayer::default());
+        app.insert_resource(CutsceneScripts::default());
+
+        cutscene_file::register_cutscene_files(app);
+
+        app.add_systems(Startup, load_cutscene_scripts);
+        app.add_systems(OnEnter(MetaStateKind::Cutscene), start_cutscene);
+        app.add_systems(
+            Update,
+            (load_cutscene, update_cutscene, update_cutscene_paths)
+                .chain()
+                .in_set(CutsceneSet)
+                .after(InputSet)
+                .after(ConvoSet)
+                .run_if(in_state(MetaStateKind::Cutscene))
+                .run_if(in_state(TransitionState::Stable)),
+        );
+        app.add_systems(OnExit(MetaStateKind::Cutscene), stop_cutscene);
+    }
+}
@@ -0,0 +1,34 @@
+//! Which file each `CutsceneState` plays. They're all loaded up front (and kept loaded), so a cutscene
+//! can start as soon as we get to it.
+
+use super::*;
+
+impl CutsceneState {
+    pub const ALL: [Self; 1] = [Self::Intro];
+
+    pub fn script_path(&self) -> &'static str {
+        match self {
+            Self::Intro => "cutscenes/intro.cutscene.ron",
+        }
+    }
+}
+
+/// Holds on to the script of every cutscene
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CutsceneScripts {
+    handles: HashMap<CutsceneState, Handle<CutsceneScript>>,
+}
+impl CutsceneScripts {
+    pub fn get_handle(&self, cutscene_state: CutsceneState) -> Option<Handle<CutsceneScript>> {
+        self.handles.get(&cutscene_state).cloned()
+    }
+}
+
+pub(super) fn load_cutscene_scripts(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(CutsceneScripts {
+        handles: CutsceneState::ALL
+            .into_iter()
+            .map(|state| (state, asset_server.load(state.script_path())))
+            .collect(),
+    });
+}
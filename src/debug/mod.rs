@@ -35,50 +35,39 @@ fn set_gizmo_config(mut config_store: ResMut<GizmoConfigStore>) {
     config.render_layers = SpriteLayer::render_layers();
 }
 
-fn debug_startup(mut commands: Commands, camera_root: Res<DynamicCameraRoot>) {
-    let room_state = RoomState::xth_encounter(EncounterKind::SimpOnly, 1);
-
-    let _ship_id = commands.spawn(ShipBundle::new(default(), &room_state)).id();
-
-    commands.spawn(PlanetBundle::new(
-        "wrap1",
-        StaticTxKind::Normal,
-        Vec2::new(0.0, room_state.room_size.y as f32 / 2.0),
-        Shape::Circle {
-            center: Vec2::ZERO,
-            radius: 15.0,
-        },
-        &room_state,
-    ));
+fn debug_room_setup(
+    mut commands: Commands,
+    room_root: Res<RoomRoot>,
+    room_state: Res<State<RoomState>>,
+) {
+    let room_state = room_state.get();
 
     let freestyle_shape = Shape::Circle {
         center: default(),
         radius: 10.0,
     };
-    let mut freestyle_shapes = vec![freestyle_shape.clone()];
-    for offset in room_state.mirage_offsets() {
-        freestyle_shapes.push(freestyle_shape.clone().with_offset(offset));
-    }
-    commands.spawn((
-        Name::new("freestyle_trigger_tx"),
-        TriggerTx::from_kind_n_shapes(TriggerKind::Ship, freestyle_shapes),
-        spat_tran!(-80.0, room_state.room_size.y as f32 / 2.0),
-    ));
-
-    spawn_stars(&mut commands, 100, 2.0, 2.0..10.0, camera_root.eid());
-
-    commands.spawn(SuicidoBundle::new(Vec2::new(0.0, -10.0), &room_state));
+    commands
+        .spawn((
+            Name::new("freestyle_trigger_tx"),
+            TriggerTx::from_kind_n_wrapped_shapes(
+                TriggerKind::Ship,
+                vec![freestyle_shape],
+                room_state,
+            ),
+            spat_tran!(-80.0, room_state.room_size.y as f32 / 2.0),
+        ))
+        .set_parent(room_root.eid());
 }
 
 fn debug_update(
     keyboard: Res<ButtonInput<KeyCode>>,
     mut commands: Commands,
     mut bullet_time: ResMut<BulletTime>,
-    mut launch: EventReader<Launch>,
-    mut fire: EventReader<Fire>,
-    mut ship: Query<(Entity, &mut DynoTran, &mut Transform), With<Ship>>,
+    mut weapons: Query<&mut ShipWeapon>,
     mut planet_textures: Query<&mut TextureManager<TextureTestPlanetState>>,
-    room_state: Res<State<RoomState>>,
+    meta_state: Res<State<MetaState>>,
+    room_root: Res<RoomRoot>,
+    mut rng: ResMut<GameRng>,
 ) {
     if keyboard.just_pressed(KeyCode::BracketLeft) {
         bullet_time.set_normal();
@@ -96,18 +85,57 @@ fn debug_update(
         }
     }
     if keyboard.just_pressed(KeyCode::Backspace) {
-        commands.spawn(SuicidoBundle::new(Vec2::new(0.0, -10.0), &room_state.get()));
+        // Room states only exist while in a room (i.e. not mid-transition)
+        if let Some(room_state) = meta_state.get_room_state() {
+            commands
+                .spawn(SuicidoBundle::new(
+                    Vec2::new(0.0, -10.0),
+                    &room_state,
+                    rng.stream(RngStream::Ai),
+                ))
+                .set_parent(room_root.eid());
+        }
     }
-    for evt in launch.read() {
-        for (eid, mut dyno_tran, mut tran) in &mut ship {
-            commands.entity(eid).remove::<Stuck>();
-            dyno_tran.vel = evt.0;
-            tran.set_angle(evt.0.to_angle());
+    let weapon_keys = [
+        (KeyCode::Digit1, WeaponKind::Single),
+        (KeyCode::Digit2, WeaponKind::Spread),
+        (KeyCode::Digit3, WeaponKind::Charge),
+    ];
+    for (key, kind) in weapon_keys {
+        if keyboard.just_pressed(key) {
+            for mut weapon in &mut weapons {
+                weapon.set_kind(kind);
+            }
         }
     }
-    for _ in fire.read() {
-        // println!("fire!");
+}
+
+/// Swaps the current room for a freshly generated one (same encounter), and logs it so it can be saved
+fn debug_generate_room(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    meta_state: Res<State<MetaState>>,
+    constants: Res<RoomGenConstants>,
+    mut rng: ResMut<GameRng>,
+    mut room_defs: ResMut<Assets<RoomDef>>,
+    mut commands: Commands,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyG) {
+        return;
     }
+    let Some(room_state) = meta_state.get_room_state() else {
+        return;
+    };
+    let params = RoomGenParams {
+        seed: rng.stream(RngStream::Environment).gen(),
+        kind: room_state.encounter_state.kind,
+        difficulty: room_state.encounter_state.difficulty,
+    };
+    let def = generate_room(params, &constants);
+    match def.to_ron() {
+        Ok(text) => info!("Generated room from {params:?}:\n{text}"),
+        Err(err) => warn!("Couldn't serialize generated room: {err}"),
+    }
+    commands.insert_resource(ActiveRoomFile::new(room_defs.add(def)));
 }
 
 pub struct DebugPlugin;
@@ -130,7 +158,11 @@ impl Plugin for DebugPlugin {
         dphysics::register_dphysics(app);
 
         // Random testing
-        app.add_systems(Startup, debug_startup.after(CameraSet));
-        app.add_systems(Update, debug_update);
+        app.add_systems(OnEnter(MetaStateKind::Room), debug_room_setup);
+        app.add_systems(Update, debug_update.run_if(in_state(PauseState::Unpaused)));
+        app.add_systems(
+            Update,
+            debug_generate_room.run_if(in_state(PauseState::Unpaused)),
+        );
     }
 }
@@ -0,0 +1,288 @@
+//! The director is what actually makes a room an encounter.
+//! On entering a room it plans out waves based on the `EncounterKind` and difficulty, spawns them,
+//! and then watches them to drive `EncounterProgress`:
+//! Entering -> Fighting (first wave arrives) -> Meandering (all waves cleared), or Dead if the ship dies.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct DirectorConstants {
+    /// How long after entering a room before the first wave shows up
+    entering_time: f32,
+    /// How long enemies spend being born (telegraphing) before they can do anything
+    enemy_birthspan: f32,
+    /// Enemies won't spawn closer than this to the ship
+    min_spawn_dist: f32,
+}
+impl Default for DirectorConstants {
+    fn default() -> Self {
+        Self {
+            entering_time: 1.0,
+            enemy_birthspan: 0.75,
+            min_spawn_dist: 80.0,
+        }
+    }
+}
+
+/// Every enemy the director has spawned. Used to know when a wave is cleared.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct EncounterEnemy;
+
+/// The kinds of enemies the director knows how to spawn
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq)]
+pub enum EncounterEnemyKind {
+    Simp,
+    Spew,
+}
+
+#[derive(Debug, Clone, Reflect)]
+pub struct EncounterWave {
+    pub enemies: Vec<EncounterEnemyKind>,
+}
+
+/// The director's plan + progress for the current room
+#[derive(Resource, Debug, Clone, Reflect, Default)]
+pub struct EncounterDirector {
+    waves: VecDeque<EncounterWave>,
+    time_entering: f32,
+}
+impl EncounterDirector {
+    /// Builds the waves for an encounter. Harder encounters have more waves with more enemies.
+    /// Boss rooms are one big mixed wave, and rest rooms have no waves at all.
+    pub fn plan(encounter_state: &EncounterState) -> Self {
+        let difficulty = encounter_state.difficulty;
+        let (num_waves, first_wave_size) = match encounter_state.kind {
+            EncounterKind::Rest => (0, 0),
+            // NOTE: There is no boss enemy yet, so it's just a lot of everything
+            EncounterKind::Boss => (1, 2 * (difficulty + 2)),
+            _ => (1 + difficulty / 2, 1 + difficulty),
+        };
+        let mut waves = VecDeque::new();
+        for wave_ix in 0..num_waves {
+            let num_enemies = first_wave_size + wave_ix;
+            let enemies = (0..num_enemies)
+                .map(|ix| match encounter_state.kind {
+                    EncounterKind::SimpOnly => EncounterEnemyKind::Simp,
+                    EncounterKind::SpewOnly => EncounterEnemyKind::Spew,
+                    EncounterKind::Both | EncounterKind::Boss | EncounterKind::Rest => {
+                        if ix % 2 == 0 {
+                            EncounterEnemyKind::Simp
+                        } else {
+                            EncounterEnemyKind::Spew
+                        }
+                    }
+                })
+                .collect();
+            waves.push_back(EncounterWave { enemies });
+        }
+        Self {
+            waves,
+            time_entering: 0.0,
+        }
+    }
+
+    pub fn waves_left(&self) -> usize {
+        self.waves.len()
+    }
+}
+
+/// Finds a spot in the room that isn't right on top of the ship.
+/// Prefers the spawn points from the room file, falling back to anywhere random.
+fn pick_spawn_pos(
+    room_state: &RoomState,
+    spawn_points: &[Vec2],
+    ship_pos: Option<Vec2>,
+    min_dist: f32,
+    rng: &mut impl Rng,
+) -> Vec2 {
+    let room_size = room_state.room_size.as_vec2();
+    let far_enough = |pos: Vec2| match ship_pos {
+        Some(ship_pos) => room_diff(pos, ship_pos, room_size).length() >= min_dist,
+        None => true,
+    };
+    let candidates: Vec<Vec2> = spawn_points
+        .iter()
+        .copied()
+        .filter(|pos| far_enough(*pos))
+        .collect();
+    if !candidates.is_empty() {
+        return candidates[rng.gen_range(0..candidates.len())];
+    }
+    let half_size = room_size / 2.0;
+    let mut pos = Vec2::ZERO;
+    // Don't try forever, tiny rooms might not have anywhere far enough away
+    for _ in 0..16 {
+        pos = Vec2::new(
+            rng.gen_range(-half_size.x..half_size.x),
+            rng.gen_range(-half_size.y..half_size.y),
+        );
+        if far_enough(pos) {
+            break;
+        }
+    }
+    pos
+}
+
+fn spawn_wave(
+    wave: EncounterWave,
+    room_state: &RoomState,
+    spawn_points: &RoomSpawnPoints,
+    ship_pos: Option<Vec2>,
+    constants: &DirectorConstants,
+    room_root: &RoomRoot,
+    rng: &mut impl Rng,
+    commands: &mut Commands,
+) {
+    for kind in wave.enemies {
+        let pos = pick_spawn_pos(
+            room_state,
+            spawn_points.get_points(),
+            ship_pos,
+            constants.min_spawn_dist,
+            rng,
+        );
+        let birthing = Birthing::new(constants.enemy_birthspan).with_suppress_physics(true);
+        let mut enemy = commands.spawn((
+            SuicidoBundle::new(pos, room_state, rng),
+            EncounterEnemy,
+            birthing,
+        ));
+        match kind {
+            EncounterEnemyKind::Simp => {}
+            // NOTE: There is no spewing enemy yet, so a suicido stands in for it. It's renamed so the
+            // stand-ins are easy to find (and swap out) in the inspector.
+            EncounterEnemyKind::Spew => {
+                enemy.insert(Name::new("spew_standin"));
+            }
+        }
+        enemy.set_parent(room_root.eid());
+    }
+}
+
+fn start_encounter(
+    mut commands: Commands,
+    room_state: Res<State<RoomState>>,
+    old_enemies: Query<Entity, With<EncounterEnemy>>,
+) {
+    for eid in &old_enemies {
+        commands.entity(eid).despawn_recursive();
+    }
+    commands.insert_resource(EncounterDirector::plan(&room_state.get().encounter_state));
+}
+
+fn update_entering(
+    mut director: ResMut<EncounterDirector>,
+    room_state: Res<State<RoomState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+    ship_q: Query<&GlobalTransform, With<Ship>>,
+    constants: Res<DirectorConstants>,
+    bullet_time: Res<BulletTime>,
+    room_root: Res<RoomRoot>,
+    spawn_points: Res<RoomSpawnPoints>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    director.time_entering += bullet_time.delta_seconds();
+    if director.time_entering < constants.entering_time {
+        return;
+    }
+    let room_state = room_state.get();
+    let ship_pos = ship_q.get_single().ok().map(|gt| gt.pos_n_angle().0);
+    if let Some(wave) = director.waves.pop_front() {
+        spawn_wave(
+            wave,
+            room_state,
+            &spawn_points,
+            ship_pos,
+            &constants,
+            &room_root,
+            rng.stream(RngStream::Ai),
+            &mut commands,
+        );
+    }
+    next_meta_state.set(
+        room_state
+            .with_progress(EncounterProgress::Fighting)
+            .to_meta_state(),
+    );
+}
+
+fn update_fighting(
+    mut director: ResMut<EncounterDirector>,
+    room_state: Res<State<RoomState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+    ship_q: Query<&GlobalTransform, With<Ship>>,
+    living: Query<Entity, With<EncounterEnemy>>,
+    constants: Res<DirectorConstants>,
+    room_root: Res<RoomRoot>,
+    spawn_points: Res<RoomSpawnPoints>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    if !living.is_empty() {
+        return;
+    }
+    let room_state = room_state.get();
+    match director.waves.pop_front() {
+        Some(wave) => {
+            let ship_pos = ship_q.get_single().ok().map(|gt| gt.pos_n_angle().0);
+            spawn_wave(
+                wave,
+                room_state,
+                &spawn_points,
+                ship_pos,
+                &constants,
+                &room_root,
+                rng.stream(RngStream::Ai),
+                &mut commands,
+            );
+        }
+        None => {
+            next_meta_state.set(
+                room_state
+                    .with_progress(EncounterProgress::Meandering)
+                    .to_meta_state(),
+            );
+        }
+    }
+}
+
+/// No matter where we are in the encounter, if the ship goes away, we're dead
+fn watch_for_ship_death(
+    mut removed_ships: RemovedComponents<Ship>,
+    room_state: Res<State<RoomState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+) {
+    if removed_ships.read().next().is_none() {
+        return;
+    }
+    next_meta_state.set(
+        room_state
+            .get()
+            .with_progress(EncounterProgress::Dead)
+            .to_meta_state(),
+    );
+}
+
+pub(super) fn register_director(app: &mut App) {
+    app.register_type::<EncounterEnemy>();
+    app.register_type::<EncounterDirector>();
+    app.insert_resource(DirectorConstants::default());
+    app.insert_resource(EncounterDirector::default());
+    debug_resource!(app, DirectorConstants);
+
+    app.add_systems(OnEnter(EncounterProgress::Entering), start_encounter);
+    app.add_systems(
+        Update,
+        (
+            update_entering.run_if(in_state(EncounterProgress::Entering)),
+            update_fighting.run_if(in_state(EncounterProgress::Fighting)),
+            watch_for_ship_death.run_if(
+                in_state(EncounterProgress::Entering)
+                    .or_else(in_state(EncounterProgress::Fighting)),
+            ),
+        )
+            .after(PhysicsSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
+}
@@ -1,7 +1,9 @@
 pub use crate::prelude::*;
 
+pub mod director;
 pub mod suicido;
 
+pub use director::*;
 pub use suicido::*;
 
 impl Patrollable for Ship {}
@@ -11,6 +13,7 @@ impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         register_patrol::<Ship, DefaultPatrollable>(app);
 
+        director::register_director(app);
         suicido::register_suicidos(app);
     }
 }
@@ -11,6 +11,9 @@ struct SuicidoConstants {
     explode_vision_box: Vec2,
     prefer_fut: f32,
     moving_away_mult: f32,
+    max_hp: u32,
+    /// How long a shot down suicido takes to go away
+    death_time: f32,
 }
 impl Default for SuicidoConstants {
     fn default() -> Self {
@@ -24,6 +27,8 @@ impl Default for SuicidoConstants {
             explode_vision_box: Vec2::new(36.0, 30.0),
             prefer_fut: 0.5,
             moving_away_mult: 3.0,
+            max_hp: 2,
+            death_time: 0.1,
         }
     }
 }
@@ -39,11 +44,12 @@ struct Charging {
     goal: ChargeGoal,
     time: f32,
 }
-impl Default for Charging {
-    fn default() -> Self {
+impl Charging {
+    /// Charging while wandering around (not targeting anything) at a random spin
+    fn wander(rng: &mut impl Rng) -> Self {
         Self {
             goal: ChargeGoal::Angle {
-                speed: thread_rng().gen_range(-0.5..0.5),
+                speed: rng.gen_range(-0.5..0.5),
             },
             time: 0.0,
         }
@@ -62,7 +68,7 @@ struct Exploding {
 }
 
 #[derive(Debug, Clone, Reflect, Component)]
-struct Suicido;
+pub struct Suicido;
 
 #[derive(Component, Debug, Clone, Reflect)]
 struct EngageVision;
@@ -78,6 +84,7 @@ pub struct SuicidoBundle {
     suicido: Suicido,
     spatial: SpatialBundle,
     static_rx: StaticRx,
+    trigger_rx: TriggerRx,
     dyno_tran: DynoTran,
     wrap: RoomWrap,
     animation: AnimationManager<AnimationSuicidoBody>,
@@ -86,7 +93,7 @@ pub struct SuicidoBundle {
     charging: Charging,
 }
 impl SuicidoBundle {
-    pub fn new(pos: Vec2, room_state: &RoomState) -> Self {
+    pub fn new(pos: Vec2, room_state: &RoomState, rng: &mut impl Rng) -> Self {
         Self {
             name: Name::new("suicido"),
             suicido: Suicido,
@@ -98,6 +105,13 @@ impl SuicidoBundle {
                     radius: 6.0,
                 },
             ),
+            trigger_rx: TriggerRx::from_kind_n_shape(
+                TriggerKind::Enemy,
+                Shape::Circle {
+                    center: Vec2::ZERO,
+                    radius: 6.0,
+                },
+            ),
             dyno_tran: default(),
             wrap: RoomWrap,
             animation: AnimationManager::new(),
@@ -106,13 +120,26 @@ impl SuicidoBundle {
                 center: Vec2::ZERO,
                 radius: 100.0,
             })),
-            charging: Charging::default(),
+            charging: Charging::wander(rng),
         }
     }
 }
 
 fn debug_suicidos() {}
 
+/// Same deal as the explode vision, suicidos get their health once they exist
+fn attach_suicido_health(
+    mut commands: Commands,
+    relevant: Query<Entity, (With<Suicido>, Without<Health>)>,
+    constants: Res<SuicidoConstants>,
+) {
+    for eid in &relevant {
+        commands
+            .entity(eid)
+            .insert(Health::new(constants.max_hp).with_death_time(constants.death_time));
+    }
+}
+
 /// So we don't have to specify the explode vision on spawn (and leak visibility of constants)
 /// do it here
 fn attach_explode_vision(
@@ -242,6 +269,7 @@ fn update_launching_suicidos(
     constants: Res<SuicidoConstants>,
     bullet_time: Res<BulletTime>,
     meta_state: Res<State<MetaState>>,
+    mut rng: ResMut<GameRng>,
 ) {
     let wrap_size = meta_state.wrap_size();
     for (eid, mut launching, gtran, engaged, explode_range) in &mut suicidos {
@@ -292,7 +320,8 @@ fn update_launching_suicidos(
                 None => moving_away
                     .map(|last_eid| ChargeGoal::Entity { eid: last_eid })
                     .unwrap_or(ChargeGoal::Angle {
-                        speed: thread_rng()
+                        speed: rng
+                            .stream(RngStream::Ai)
                             .gen_range((-constants.rot_speed)..(constants.rot_speed)),
                     }),
             };
@@ -316,6 +345,7 @@ fn update_exploding_suicidos(
         With<Suicido>,
     >,
     meta_state: Res<State<MetaState>>,
+    mut explosion_pool: ResMut<EntityPool<ExplosionCircle>>,
 ) {
     for (eid, mut exploding, gtran, dyno_tran, animation_state, animation_progress) in &mut suicidos
     {
@@ -334,11 +364,15 @@ fn update_exploding_suicidos(
                     == Some(3)
             {
                 exploding.has_spawned_circle = true;
-                commands.spawn(ExplosionCircleBundle::new(
-                    gtran.pos_n_angle().0,
-                    dyno_tran,
-                    &meta_state.get_room_state().unwrap(),
-                ));
+                if let Some(circle_eid) = explosion_pool.acquire(&mut commands, None) {
+                    commands
+                        .entity(circle_eid)
+                        .insert(ExplosionCircleBundle::new(
+                            gtran.pos_n_angle().0,
+                            dyno_tran,
+                            &meta_state.play_room_state().unwrap(),
+                        ));
+                }
             }
         }
     }
@@ -350,9 +384,14 @@ fn update_suicido_animations(
         Option<&Charging>,
         Option<&Launching>,
         Option<&Exploding>,
+        Has<DamageFlash>,
     )>,
 ) {
-    for (mut manager, charging, launching, exploding) in &mut suicidos {
+    for (mut manager, charging, launching, exploding, hurt) in &mut suicidos {
+        if hurt && exploding.is_none() {
+            manager.set_state(AnimationSuicidoBody::Hurt);
+            continue;
+        }
         match (charging, launching, exploding) {
             (Some(_), None, None) => manager.set_state(AnimationSuicidoBody::Charge),
             (None, Some(_), None) => manager.set_state(AnimationSuicidoBody::Launch),
@@ -374,6 +413,8 @@ struct ExplosionCircleBundle {
     wrap: RoomWrap,
     animation: AnimationManager<AnimationSuicidoExplosionCircle>,
     mirage: MirageAnimationManager,
+    trigger_tx: TriggerTx,
+    damage: Damage,
 }
 impl ExplosionCircleBundle {
     fn new(pos: Vec2, dyno_tran: &DynoTran, room_state: &RoomState) -> Self {
@@ -385,6 +426,15 @@ impl ExplosionCircleBundle {
             wrap: RoomWrap,
             animation: AnimationManager::new(),
             mirage: MirageAnimationManager::room_offsets(room_state),
+            trigger_tx: TriggerTx::from_kind_n_wrapped_shapes(
+                TriggerKind::Explosion,
+                vec![Shape::Circle {
+                    center: Vec2::ZERO,
+                    radius: 20.0,
+                }],
+                room_state,
+            ),
+            damage: Damage::new(1, vec![TriggerKind::Ship]).with_knockback(60.0),
         }
     }
 }
@@ -396,6 +446,7 @@ pub(super) fn register_suicidos(app: &mut App) {
         (
             debug_suicidos,
             attach_explode_vision,
+            attach_suicido_health,
             update_charging_suicidos,
             update_launching_suicidos,
             update_exploding_suicidos,
@@ -405,6 +456,7 @@ pub(super) fn register_suicidos(app: &mut App) {
     );
     app.add_systems(BulletUpdate, drag_charging_suicidos);
     app.insert_resource(SuicidoConstants::default());
+    register_pool::<ExplosionCircle>(app, 16, PoolOverflow::Grow);
     debug_resource!(app, SuicidoConstants);
     register_patrol::<Ship, EngageVision>(app);
     register_patrol::<Ship, ExplodeVision>(app);
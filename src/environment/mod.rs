@@ -1,14 +1,20 @@
 use crate::prelude::*;
 
 pub mod planet;
+pub mod room_file;
+pub mod room_gen;
 pub mod star;
 
 pub use planet::*;
+pub use room_file::*;
+pub use room_gen::*;
 pub use star::*;
 
 pub(super) struct EnvironmentPlugin;
 impl Plugin for EnvironmentPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(star::StarPlugin);
+        room_file::register_room_files(app);
+        room_gen::register_room_gen(app);
     }
 }
@@ -15,18 +15,13 @@ impl PlanetBundle {
         pos: Vec2,
         shape: Shape,
         room_state: &RoomState,
+        rng: &mut impl Rng,
     ) -> Self {
         let canonical_points = shape.to_points();
-        let mirage_shapes = room_state
-            .mirage_offsets()
-            .into_iter()
-            .map(|p| shape.clone().with_offset(p));
-        let mut all_shapes: Vec<Shape> = vec![shape.clone()];
-        all_shapes.extend(mirage_shapes);
         Self {
             name: Name::new(name.to_string()),
-            spatial: spat_tran!(pos.x, pos.y, ZIX_PLANET + zix_nudge()),
-            static_tx: StaticTx::from_kind_n_shapes(tx_kind, all_shapes),
+            spatial: spat_tran!(pos.x, pos.y, ZIX_PLANET + zix_nudge(rng)),
+            static_tx: StaticTx::from_kind_n_wrapped_shapes(tx_kind, vec![shape], room_state),
             texture: TextureManager::new()
                 .with_part_points(
                     TextureTestPlanetPart::Inner,
@@ -36,4 +31,9 @@ impl PlanetBundle {
             mirage_texture: MirageTextureManager::room_offsets(&room_state),
         }
     }
+
+    pub fn with_texture_state(mut self, state: TextureTestPlanetState) -> Self {
+        self.texture = self.texture.with_state(state);
+        self
+    }
 }
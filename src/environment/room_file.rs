@@ -0,0 +1,360 @@
+//! Rooms are defined in `.room.ron` files under `assets/rooms`.
+//! A file describes everything that's static about a room (size, planets, where enemies can show up,
+//! what the star field looks like, what kind of encounter it is). Spawning is done with the normal bundles.
+//!
+//! Set the `ActiveRoomFile` to start loading one. Once it's loaded (and every time it's modified, if the
+//! `hot_reload` feature is on) the old content is cleared and the room is respawned.
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+
+use crate::prelude::*;
+
+/// The textures a planet in a room file can have
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub enum PlanetTextureDef {
+    #[default]
+    BlueInner,
+    RedInner,
+}
+impl From<PlanetTextureDef> for TextureTestPlanetState {
+    fn from(value: PlanetTextureDef) -> Self {
+        match value {
+            PlanetTextureDef::BlueInner => TextureTestPlanetState::BlueInner,
+            PlanetTextureDef::RedInner => TextureTestPlanetState::RedInner,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetDef {
+    pub name: String,
+    pub pos: Vec2,
+    pub kind: StaticTxKind,
+    /// NOTE: Relative to `pos`
+    pub shape: Shape,
+    #[serde(default)]
+    pub texture: PlanetTextureDef,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarFieldDef {
+    pub count: u32,
+    pub wrap: f32,
+    pub min_dist: f32,
+    pub max_dist: f32,
+}
+impl Default for StarFieldDef {
+    fn default() -> Self {
+        Self {
+            count: 100,
+            wrap: 2.0,
+            min_dist: 2.0,
+            max_dist: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncounterDef {
+    pub kind: EncounterKind,
+    pub difficulty: u32,
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct RoomDef {
+    pub size: UVec2,
+    pub encounter: EncounterDef,
+    /// Where the ship starts
+    #[serde(default)]
+    pub ship_pos: Vec2,
+    #[serde(default)]
+    pub planets: Vec<PlanetDef>,
+    /// Places the director is allowed to spawn enemies. If empty, it'll pick random spots.
+    #[serde(default)]
+    pub spawn_points: Vec<Vec2>,
+    #[serde(default)]
+    pub stars: StarFieldDef,
+    /// Conversation (see `convo`) that starts when the room does
+    #[serde(default)]
+    pub convo: Option<String>,
+}
+impl RoomDef {
+    /// The state to be in when playing this room (from the start)
+    pub fn room_state(&self) -> RoomState {
+        RoomState {
+            room_size: self.size,
+            encounter_state: EncounterState {
+                kind: self.encounter.kind,
+                difficulty: self.encounter.difficulty,
+                progress: EncounterProgress::Entering,
+            },
+        }
+    }
+
+    /// What this room would look like as a `.room.ron` file
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+}
+
+#[derive(Debug)]
+pub enum RoomDefLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+impl std::fmt::Display for RoomDefLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read room file: {err}"),
+            Self::Ron(err) => write!(f, "couldn't parse room file: {err}"),
+        }
+    }
+}
+impl std::error::Error for RoomDefLoaderError {}
+impl From<std::io::Error> for RoomDefLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for RoomDefLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+#[derive(Default)]
+struct RoomDefLoader;
+impl AssetLoader for RoomDefLoader {
+    type Asset = RoomDef;
+    type Settings = ();
+    type Error = RoomDefLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<RoomDef>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["room.ron"]
+    }
+}
+
+/// The room file that should currently be spawned (if any)
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ActiveRoomFile {
+    handle: Option<Handle<RoomDef>>,
+}
+impl ActiveRoomFile {
+    pub fn new(handle: Handle<RoomDef>) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+
+    pub fn get_handle(&self) -> Option<&Handle<RoomDef>> {
+        self.handle.as_ref()
+    }
+
+    /// The asset path of the room file, i.e. "rooms/debug.room.ron"
+    pub fn get_path(&self) -> Option<String> {
+        self.handle
+            .as_ref()
+            .and_then(|handle| handle.path())
+            .map(|path| path.to_string())
+    }
+}
+
+/// Where the director can spawn enemies in the current room
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct RoomSpawnPoints {
+    points: Vec<Vec2>,
+}
+impl RoomSpawnPoints {
+    pub fn get_points(&self) -> &[Vec2] {
+        &self.points
+    }
+}
+
+/// Marks everything that was spawned from a room file, so it can be cleaned up on reload
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct RoomFileContent;
+
+/// Clears out whatever was spawned for the last room file and spawns this one.
+/// The ship is left alone if it already exists (so hot reloading doesn't kill it).
+fn spawn_room_def(
+    def: &RoomDef,
+    old_content: &Query<Entity, With<RoomFileContent>>,
+    has_ship: bool,
+    commands: &mut Commands,
+    room_root: &RoomRoot,
+    camera_root: &DynamicCameraRoot,
+    rng: &mut GameRng,
+) {
+    for eid in old_content {
+        commands.entity(eid).despawn_recursive();
+    }
+    let room_state = def.room_state();
+    if !has_ship {
+        commands
+            .spawn(ShipBundle::new(def.ship_pos, &room_state))
+            .set_parent(room_root.eid());
+    }
+    for planet in &def.planets {
+        commands
+            .spawn((
+                PlanetBundle::new(
+                    &planet.name,
+                    planet.kind,
+                    planet.pos,
+                    planet.shape.clone(),
+                    &room_state,
+                    rng.stream(RngStream::Cosmetic),
+                )
+                .with_texture_state(planet.texture.into()),
+                RoomFileContent,
+            ))
+            .set_parent(room_root.eid());
+    }
+    for _ in 0..def.stars.count {
+        commands
+            .spawn((
+                StarBundle::random(
+                    def.stars.wrap,
+                    &(def.stars.min_dist..def.stars.max_dist),
+                    rng.stream(RngStream::Cosmetic),
+                ),
+                RoomFileContent,
+            ))
+            .set_parent(camera_root.eid());
+    }
+    commands.insert_resource(RoomSpawnPoints {
+        points: def.spawn_points.clone(),
+    });
+}
+
+/// Spawns the active room whenever it finishes loading or gets changed on disk
+fn watch_room_files(
+    mut asset_events: EventReader<AssetEvent<RoomDef>>,
+    active: Res<ActiveRoomFile>,
+    defs: Res<Assets<RoomDef>>,
+    old_content: Query<Entity, With<RoomFileContent>>,
+    ship_q: Query<(), With<Ship>>,
+    meta_state: Res<State<MetaState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+    room_root: Res<RoomRoot>,
+    camera_root: Res<DynamicCameraRoot>,
+    mut convos: EventWriter<StartConvo>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let Some(handle) = active.get_handle() else {
+        return;
+    };
+    let mut should_spawn = active.is_changed() && defs.contains(handle);
+    let mut was_modified = false;
+    for event in asset_events.read() {
+        match event {
+            AssetEvent::LoadedWithDependencies { id } => {
+                should_spawn = should_spawn || *id == handle.id();
+            }
+            AssetEvent::Modified { id } => {
+                should_spawn = should_spawn || *id == handle.id();
+                was_modified = was_modified || *id == handle.id();
+            }
+            _ => {}
+        }
+    }
+    if !should_spawn {
+        return;
+    }
+    let Some(def) = defs.get(handle) else {
+        return;
+    };
+    // Outside of rooms there's nothing to do, it'll get spawned on entering one
+    let Some(current) = meta_state.get_room_state() else {
+        return;
+    };
+    spawn_room_def(
+        def,
+        &old_content,
+        !ship_q.is_empty(),
+        &mut commands,
+        &room_root,
+        &camera_root,
+        &mut rng,
+    );
+    let room_state = def.room_state();
+    if was_modified {
+        // Someone is editing the file, restart the room exactly as it's written
+        if current.room_size != room_state.room_size
+            || current.encounter_state.kind != room_state.encounter_state.kind
+            || current.encounter_state.difficulty != room_state.encounter_state.difficulty
+        {
+            next_meta_state.set(room_state.to_meta_state());
+        }
+    } else {
+        // Otherwise the encounter is whatever we're progressing through, but the geometry comes from the file
+        if current.room_size != room_state.room_size {
+            next_meta_state.set(current.with_room_size(room_state.room_size).to_meta_state());
+        }
+        start_room_convo(def, &mut convos);
+    }
+}
+
+fn start_room_convo(def: &RoomDef, convos: &mut EventWriter<StartConvo>) {
+    if let Some(path) = &def.convo {
+        convos.send(StartConvo { path: path.clone() });
+    }
+}
+
+fn spawn_room_on_enter(
+    active: Res<ActiveRoomFile>,
+    defs: Res<Assets<RoomDef>>,
+    old_content: Query<Entity, With<RoomFileContent>>,
+    ship_q: Query<(), With<Ship>>,
+    room_root: Res<RoomRoot>,
+    camera_root: Res<DynamicCameraRoot>,
+    mut convos: EventWriter<StartConvo>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    // If it's not loaded yet, `watch_room_files` will spawn it once it is
+    let Some(def) = active.get_handle().and_then(|handle| defs.get(handle)) else {
+        return;
+    };
+    spawn_room_def(
+        def,
+        &old_content,
+        !ship_q.is_empty(),
+        &mut commands,
+        &room_root,
+        &camera_root,
+        &mut rng,
+    );
+    start_room_convo(def, &mut convos);
+}
+
+fn despawn_room_on_exit(old_content: Query<Entity, With<RoomFileContent>>, mut commands: Commands) {
+    for eid in &old_content {
+        commands.entity(eid).despawn_recursive();
+    }
+}
+
+pub(super) fn register_room_files(app: &mut App) {
+    app.init_asset::<RoomDef>();
+    app.init_asset_loader::<RoomDefLoader>();
+    app.register_type::<RoomFileContent>();
+    app.register_type::<RoomSpawnPoints>();
+    app.insert_resource(ActiveRoomFile::default());
+    app.insert_resource(RoomSpawnPoints::default());
+
+    app.add_systems(Update, watch_room_files.after(PhysicsSet));
+    app.add_systems(OnEnter(MetaStateKind::Room), spawn_room_on_enter);
+    app.add_systems(OnExit(MetaStateKind::Room), despawn_room_on_exit);
+}
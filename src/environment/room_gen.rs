@@ -0,0 +1,359 @@
+//! Procedural rooms. Given a seed, `EncounterKind` and difficulty, `generate_room` lays out a `RoomDef`,
+//! the same thing a `.room.ron` file describes. That means a generated room can be inspected, written out
+//! (see `RoomDef::to_ron`) and spawned exactly like a hand-authored one.
+//!
+//! Generation guarantees:
+//! - Planets never overlap, including across the wrap seam (distances are measured with `room_diff`)
+//! - Nothing is within `ship_clearance` of where the ship starts
+//! - There's always at least one sticky planet, so the ship has somewhere to land
+//!
+//! The same seed (and constants) always makes the same room.
+
+use crate::prelude::*;
+
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct RoomGenConstants {
+    pub room_size: UVec2,
+    /// Planets in a difficulty 0 room. Each level of difficulty adds one.
+    pub base_planets: u32,
+    pub max_planets: u32,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    /// Chance a planet is an irregular polygon instead of a circle
+    pub polygon_chance: f32,
+    /// Empty space between any two planets (so the ship can fit through)
+    pub planet_gap: f32,
+    /// Empty space around where the ship starts
+    pub ship_clearance: f32,
+    pub num_spawn_points: u32,
+    /// Spawn points will be at least this far from planets
+    pub spawn_point_clearance: f32,
+    /// How many random spots to try before giving up on placing something
+    pub max_attempts: u32,
+}
+impl Default for RoomGenConstants {
+    fn default() -> Self {
+        Self {
+            room_size: UVec2::new(640, 360),
+            base_planets: 4,
+            max_planets: 10,
+            min_radius: 12.0,
+            max_radius: 40.0,
+            polygon_chance: 0.4,
+            planet_gap: 24.0,
+            ship_clearance: 50.0,
+            num_spawn_points: 4,
+            spawn_point_clearance: 12.0,
+            max_attempts: 64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct RoomGenParams {
+    pub seed: u64,
+    pub kind: EncounterKind,
+    pub difficulty: u32,
+}
+
+/// A planet that's been placed, kept around to check new ones against
+struct Placed {
+    pos: Vec2,
+    /// Radius of a circle that contains the whole shape
+    bound: f32,
+}
+
+/// How much of the room should be sticky. Simps are easier to fight from a perch, spewers from behind cover.
+/// Harder rooms have fewer places to rest.
+fn sticky_fraction(kind: EncounterKind, difficulty: u32) -> f32 {
+    let base = match kind {
+        EncounterKind::SimpOnly => 0.6,
+        EncounterKind::SpewOnly => 0.3,
+        EncounterKind::Both => 0.45,
+        EncounterKind::Boss => 0.3,
+        EncounterKind::Rest => 0.8,
+    };
+    (base - 0.05 * difficulty as f32).max(0.2)
+}
+
+/// A convex, lopsided polygon that fits in a circle of `radius`.
+/// Made by taking jittered points around an ellipse, so it's always convex and clockwise.
+fn irregular_polygon(radius: f32, rng: &mut impl Rng) -> Shape {
+    let num_points = rng.gen_range(5..=9);
+    let step = 2.0 * PI / num_points as f32;
+    let squash = rng.gen_range(0.6..1.0);
+    let rot = rng.gen_range(0.0..(2.0 * PI));
+    let points = (0..num_points)
+        .map(|ix| {
+            // Going down in angle keeps the points clockwise
+            let angle = -(ix as f32 * step + rng.gen_range((-0.3 * step)..(0.3 * step)));
+            Vec2::new(angle.cos() * radius, angle.sin() * radius * squash).my_rotate(rot)
+        })
+        .collect();
+    Shape::Polygon { points }
+}
+
+/// Tries random spots until one is far enough from the ship and `clearance` away from every placed planet
+fn find_spot(
+    room_size: Vec2,
+    ship_pos: Vec2,
+    placed: &[Placed],
+    bound: f32,
+    clearance: f32,
+    constants: &RoomGenConstants,
+    rng: &mut impl Rng,
+) -> Option<Vec2> {
+    let half_size = room_size / 2.0;
+    for _ in 0..constants.max_attempts {
+        let pos = Vec2::new(
+            rng.gen_range(-half_size.x..half_size.x),
+            rng.gen_range(-half_size.y..half_size.y),
+        );
+        if room_diff(pos, ship_pos, room_size).length() < bound + constants.ship_clearance {
+            continue;
+        }
+        let hits_planet = placed.iter().any(|other| {
+            room_diff(pos, other.pos, room_size).length() < bound + other.bound + clearance
+        });
+        if !hits_planet {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+pub fn generate_room(params: RoomGenParams, constants: &RoomGenConstants) -> RoomDef {
+    let mut rng = StdRng::seed_from_u64(params.seed);
+    let room_size = constants.room_size.as_vec2();
+    let ship_pos = Vec2::ZERO;
+    // A planet can't be so big that it would touch its own copy on the other side of the seam
+    let max_radius = constants
+        .max_radius
+        .min((room_size.min_element() - constants.planet_gap) / 2.0)
+        .max(constants.min_radius);
+    let num_planets =
+        (constants.base_planets + params.difficulty).min(constants.max_planets) as usize;
+    let sticky_fraction = sticky_fraction(params.kind, params.difficulty);
+
+    let mut placed: Vec<Placed> = vec![];
+    let mut planets: Vec<PlanetDef> = vec![];
+    for _ in 0..num_planets {
+        let radius = rng.gen_range(constants.min_radius..=max_radius);
+        let Some(pos) = find_spot(
+            room_size,
+            ship_pos,
+            &placed,
+            radius,
+            constants.planet_gap,
+            constants,
+            &mut rng,
+        ) else {
+            // The room is full enough, everything placed so far is still valid
+            continue;
+        };
+        let shape = if rng.gen_bool(constants.polygon_chance as f64) {
+            irregular_polygon(radius, &mut rng)
+        } else {
+            Shape::Circle {
+                center: Vec2::ZERO,
+                radius,
+            }
+        };
+        // The first planet is always sticky so there's somewhere to land
+        let kind = if planets.is_empty() || rng.gen_bool(sticky_fraction as f64) {
+            StaticTxKind::Sticky
+        } else {
+            StaticTxKind::Normal
+        };
+        let texture = match kind {
+            StaticTxKind::Sticky => PlanetTextureDef::RedInner,
+            StaticTxKind::Normal => PlanetTextureDef::BlueInner,
+        };
+        planets.push(PlanetDef {
+            name: format!("planet{}", planets.len()),
+            pos,
+            kind,
+            shape,
+            texture,
+        });
+        placed.push(Placed { pos, bound: radius });
+    }
+
+    let mut spawn_points = vec![];
+    for _ in 0..constants.num_spawn_points {
+        if let Some(pos) = find_spot(
+            room_size,
+            ship_pos,
+            &placed,
+            0.0,
+            constants.spawn_point_clearance,
+            constants,
+            &mut rng,
+        ) {
+            spawn_points.push(pos);
+        }
+    }
+
+    RoomDef {
+        size: constants.room_size,
+        encounter: EncounterDef {
+            kind: params.kind,
+            difficulty: params.difficulty,
+        },
+        ship_pos,
+        planets,
+        spawn_points,
+        stars: default(),
+        convo: None,
+    }
+}
+
+pub(super) fn register_room_gen(app: &mut App) {
+    app.insert_resource(RoomGenConstants::default());
+    debug_resource!(app, RoomGenConstants);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [EncounterKind; 5] = [
+        EncounterKind::SimpOnly,
+        EncounterKind::SpewOnly,
+        EncounterKind::Both,
+        EncounterKind::Boss,
+        EncounterKind::Rest,
+    ];
+
+    /// Every combination of kind and difficulty for a bunch of seeds
+    fn all_rooms() -> impl Iterator<Item = (RoomGenParams, RoomDef)> {
+        let constants = RoomGenConstants::default();
+        (0..20).flat_map(move |seed| {
+            let constants = constants.clone();
+            KINDS.into_iter().flat_map(move |kind| {
+                let constants = constants.clone();
+                (0..8).map(move |difficulty| {
+                    let params = RoomGenParams {
+                        seed,
+                        kind,
+                        difficulty,
+                    };
+                    (params, generate_room(params, &constants))
+                })
+            })
+        })
+    }
+
+    /// Radius of a circle (around the planet's pos) that contains the whole shape
+    fn bound(shape: &Shape) -> f32 {
+        match shape {
+            Shape::Circle { center, radius } => center.length() + radius,
+            Shape::Polygon { points } => points.iter().map(|p| p.length()).fold(0.0, f32::max),
+        }
+    }
+
+    #[test]
+    fn same_seed_same_room() {
+        let constants = RoomGenConstants::default();
+        let params = RoomGenParams {
+            seed: 7,
+            kind: EncounterKind::Both,
+            difficulty: 3,
+        };
+        let first = generate_room(params, &constants).to_ron().unwrap();
+        let second = generate_room(params, &constants).to_ron().unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn different_seeds_different_rooms() {
+        let constants = RoomGenConstants::default();
+        let room = |seed| {
+            generate_room(
+                RoomGenParams {
+                    seed,
+                    kind: EncounterKind::Both,
+                    difficulty: 3,
+                },
+                &constants,
+            )
+            .to_ron()
+            .unwrap()
+        };
+        assert_ne!(room(1), room(2));
+    }
+
+    #[test]
+    fn matches_params() {
+        let constants = RoomGenConstants::default();
+        for (params, room) in all_rooms() {
+            assert_eq!(room.size, constants.room_size);
+            assert_eq!(room.encounter.kind, params.kind);
+            assert_eq!(room.encounter.difficulty, params.difficulty);
+            assert!(room.planets.len() <= constants.max_planets as usize);
+            assert!(room.spawn_points.len() <= constants.num_spawn_points as usize);
+        }
+    }
+
+    #[test]
+    fn everything_inside_room() {
+        let half_size = RoomGenConstants::default().room_size.as_vec2() / 2.0;
+        let inside = |pos: Vec2| pos.abs().cmple(half_size).all();
+        for (params, room) in all_rooms() {
+            for planet in &room.planets {
+                assert!(inside(planet.pos), "{params:?}: {} outside", planet.name);
+            }
+            for pos in &room.spawn_points {
+                assert!(inside(*pos), "{params:?}: spawn point {pos} outside");
+            }
+        }
+    }
+
+    #[test]
+    fn planets_dont_overlap() {
+        let constants = RoomGenConstants::default();
+        let room_size = constants.room_size.as_vec2();
+        for (params, room) in all_rooms() {
+            for (ix, planet) in room.planets.iter().enumerate() {
+                for other in &room.planets[ix + 1..] {
+                    let dist = room_diff(planet.pos, other.pos, room_size).length();
+                    let needed = bound(&planet.shape) + bound(&other.shape) + constants.planet_gap;
+                    assert!(
+                        dist >= needed - 0.01,
+                        "{params:?}: {} and {} overlap",
+                        planet.name,
+                        other.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ship_has_room() {
+        let constants = RoomGenConstants::default();
+        let room_size = constants.room_size.as_vec2();
+        for (params, room) in all_rooms() {
+            for planet in &room.planets {
+                let dist = room_diff(planet.pos, room.ship_pos, room_size).length();
+                assert!(
+                    dist >= bound(&planet.shape) + constants.ship_clearance - 0.01,
+                    "{params:?}: {} too close to the ship",
+                    planet.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn somewhere_to_land() {
+        for (params, room) in all_rooms() {
+            assert!(
+                room.planets
+                    .iter()
+                    .any(|planet| planet.kind == StaticTxKind::Sticky),
+                "{params:?}: no sticky planet"
+            );
+        }
+    }
+}
@@ -13,8 +13,7 @@ pub struct StarBundle {
     animation: AnimationManager<AnimationStar>,
 }
 impl StarBundle {
-    pub fn random(wrap: f32, dist_range: &Range<f32>) -> Self {
-        let mut rng = thread_rng();
+    pub fn random(wrap: f32, dist_range: &Range<f32>, rng: &mut impl Rng) -> Self {
         let distance = rng.gen_range(dist_range.clone());
         let wrap_size = IDEAL_VEC_f32 * wrap;
         Self {
@@ -43,9 +42,10 @@ pub fn spawn_stars(
     wrap: f32,
     dist_range: Range<f32>,
     parent: Entity,
+    rng: &mut impl Rng,
 ) {
     for _ in 0..num_stars {
-        let bund = StarBundle::random(wrap, &dist_range);
+        let bund = StarBundle::random(wrap, &dist_range, rng);
         commands.spawn(bund).set_parent(parent);
     }
 }
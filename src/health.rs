@@ -0,0 +1,223 @@
+//! Hit points for anything that can get hurt (the ship, enemies).
+//!
+//! Hurtboxes are just the `TriggerRx` of an entity with `Health`. Hitboxes are a `TriggerTx` with `Damage`,
+//! which says how much it hurts and which kinds of hurtboxes it hurts. Every overlap turns into a `DealDamage`
+//! event (anything else that wants to hurt something can send one too). Applying damage:
+//! - Takes hit points, then makes the victim invulnerable for its `invulnerable_time`
+//! - Knocks the victim away from whatever hit it (if it moves)
+//! - Marks it hurt for a moment (see `DamageFlash`), which its animation should show (i.e. with a state
+//!   that uses `override_color`)
+//! - At zero hit points, starts the victim `Dying` with `DeathCause::Damage`
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct HealthConstants {
+    flash_time: f32,
+}
+impl Default for HealthConstants {
+    fn default() -> Self {
+        Self { flash_time: 0.3 }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Health {
+    hp: u32,
+    max_hp: u32,
+    /// How long after taking a hit nothing else can hurt this
+    invulnerable_time: f32,
+    invulnerable: f32,
+    /// How long the `Dying` lasts when this runs out
+    death_time: f32,
+}
+impl Health {
+    pub fn new(max_hp: u32) -> Self {
+        Self {
+            hp: max_hp,
+            max_hp,
+            invulnerable_time: 0.0,
+            invulnerable: 0.0,
+            death_time: 0.0,
+        }
+    }
+
+    impl_get!(hp, u32);
+    impl_get!(max_hp, u32);
+    impl_get_set_with!(invulnerable_time, f32);
+    impl_get_set_with!(death_time, f32);
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable > 0.0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.hp == 0
+    }
+
+    /// Growing comes with the new hit points already healed, shrinking only takes away what's over
+    pub fn set_max_hp(&mut self, max_hp: u32) {
+        if max_hp > self.max_hp {
+            self.hp += max_hp - self.max_hp;
+        } else {
+            self.hp = self.hp.min(max_hp);
+        }
+        self.max_hp = max_hp;
+    }
+}
+
+/// Put this next to a `TriggerTx` to make it a hitbox
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Damage {
+    amount: u32,
+    /// Speed added to the victim, directed away from this
+    knockback: f32,
+    /// The kinds of hurtboxes (`TriggerRx`) this hurts
+    victims: Vec<TriggerKind>,
+}
+impl Damage {
+    pub fn new(amount: u32, victims: Vec<TriggerKind>) -> Self {
+        Self {
+            amount,
+            knockback: 0.0,
+            victims,
+        }
+    }
+
+    impl_get!(amount, u32);
+    impl_get_set_with!(knockback, f32);
+}
+
+/// Send this to hurt something with `Health`
+#[derive(Event, Debug, Clone)]
+pub struct DealDamage {
+    pub target: Entity,
+    pub amount: u32,
+    /// The thing that did the damage (if any)
+    pub source: Option<Entity>,
+    /// Added straight to the target's velocity
+    pub knockback: Vec2,
+}
+
+/// On things that were just hurt, for as long as they should look hurt
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct DamageFlash {
+    time: f32,
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HealthSet;
+
+/// Turns hitbox/hurtbox overlaps into damage
+fn watch_hitboxes(
+    hurtboxes: Query<(Entity, &TriggerRx, &GlobalTransform), With<Health>>,
+    hitboxes: Query<(&Damage, &GlobalTransform)>,
+    records: Query<&TriggerCollisionRecord>,
+    mut damage: EventWriter<DealDamage>,
+) {
+    for (eid, rx, gtran) in &hurtboxes {
+        for record_eid in &rx.collisions {
+            let Ok(record) = records.get(*record_eid) else {
+                continue;
+            };
+            let Ok((hitbox, hitbox_gtran)) = hitboxes.get(record.tx_eid) else {
+                continue;
+            };
+            if !hitbox.victims.contains(&rx.kind) {
+                continue;
+            }
+            let away = (gtran.translation() - hitbox_gtran.translation())
+                .truncate()
+                .normalize_or_zero();
+            damage.send(DealDamage {
+                target: eid,
+                amount: hitbox.amount,
+                source: Some(record.tx_eid),
+                knockback: away * hitbox.knockback,
+            });
+        }
+    }
+}
+
+fn apply_damage(
+    mut damage: EventReader<DealDamage>,
+    mut victims: Query<(&mut Health, Option<&mut DynoTran>), Without<Dying>>,
+    constants: Res<HealthConstants>,
+    mut commands: Commands,
+) {
+    for event in damage.read() {
+        let Ok((mut health, dyno_tran)) = victims.get_mut(event.target) else {
+            continue;
+        };
+        if health.is_dead() || health.is_invulnerable() {
+            continue;
+        }
+        health.hp = health.hp.saturating_sub(event.amount);
+        health.invulnerable = health.invulnerable_time;
+        if let Some(mut dyno_tran) = dyno_tran {
+            if event.knockback != Vec2::ZERO {
+                dyno_tran.vel += event.knockback;
+                commands.entity(event.target).remove::<Stuck>();
+            }
+        }
+        commands.entity(event.target).insert(DamageFlash {
+            time: constants.flash_time,
+        });
+        if health.is_dead() {
+            commands
+                .entity(event.target)
+                .insert(
+                    Dying::new(health.death_time).with_cause(DeathCause::Damage {
+                        source: event.source,
+                    }),
+                );
+        }
+    }
+}
+
+fn update_invulnerability(mut healths: Query<&mut Health>, bullet_time: Res<BulletTime>) {
+    for mut health in &mut healths {
+        if health.invulnerable > 0.0 {
+            health.invulnerable = (health.invulnerable - bullet_time.delta_seconds()).max(0.0);
+        }
+    }
+}
+
+fn update_damage_flashes(
+    mut flashes: Query<(Entity, &mut DamageFlash)>,
+    bullet_time: Res<BulletTime>,
+    mut commands: Commands,
+) {
+    for (eid, mut flash) in &mut flashes {
+        flash.time -= bullet_time.delta_seconds();
+        if flash.time <= 0.0 {
+            commands.entity(eid).remove::<DamageFlash>();
+        }
+    }
+}
+
+pub(super) struct HealthPlugin;
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Health>();
+        app.register_type::<Damage>();
+        app.register_type::<DamageFlash>();
+        app.add_event::<DealDamage>();
+        app.insert_resource(HealthConstants::default());
+        debug_resource!(app, HealthConstants);
+
+        app.add_systems(
+            Update,
+            (
+                watch_hitboxes,
+                apply_damage,
+                update_invulnerability,
+                update_damage_flashes,
+            )
+                .chain()
+                .in_set(HealthSet)
+                .after(PhysicsSet)
+                .run_if(in_state(PhysicsState::Active)),
+        );
+    }
+}
@@ -79,12 +79,30 @@ fn update_convo_input(
 /// such input per frame
 fn update_non_game_input(
     keyboard: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    drag_input: Res<DragInput>,
     mut non_game_writer: EventWriter<NonGameInput>,
 ) {
     if keyboard.just_pressed(KeyCode::Enter) {
         non_game_writer.send(NonGameInput::Continue);
         return;
     }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        non_game_writer.send(NonGameInput::Pause);
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        non_game_writer.send(NonGameInput::Up);
+        return;
+    }
+    if keyboard.any_just_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        non_game_writer.send(NonGameInput::Down);
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Left) {
+        non_game_writer.send(NonGameInput::Press(drag_input.get_screen_pos()));
+        return;
+    }
 }
 
 pub struct DesktopInputPlugin;
@@ -92,7 +110,12 @@ impl Plugin for DesktopInputPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (update_drag_input, update_convo_input, update_non_game_input),
+            (
+                update_drag_input,
+                update_convo_input,
+                update_non_game_input.after(update_drag_input),
+            )
+                .in_set(InputSet),
         );
     }
 }
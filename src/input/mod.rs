@@ -1,8 +1,10 @@
 use crate::prelude::*;
 
 pub mod desktop_input;
+pub mod touch_input;
 
 pub use desktop_input::*;
+pub use touch_input::*;
 
 // Any place in the app that needs to react to input should use these events and resources
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
@@ -36,7 +38,7 @@ impl DragInput {
 
 /// Event that corresponds to input that _should_ send the ship flying.
 /// NOTE: It is the responsibility of other systems to monitor the ship resources,
-/// i.e. determine if it actually CAN go flying rn
+/// i.e. determine if it actually CAN go flying rn (see `ship/launch.rs`)
 #[derive(Event)]
 pub struct Launch(pub Vec2);
 
@@ -50,10 +52,19 @@ pub struct Fire(pub Vec2);
 #[derive(Event)]
 pub struct ConvoGoNext;
 
-/// Rn used to continue on some menu screens, maybe will expand to custom UI? idk
-#[derive(Event)]
+/// Input for menus and other things that aren't gameplay
+#[derive(Event, Debug, Clone)]
 pub enum NonGameInput {
+    /// Advance / select whatever is focused
     Continue,
+    /// Toggle pausing
+    Pause,
+    /// Move focus up
+    Up,
+    /// Move focus down
+    Down,
+    /// Pressed at a point on the screen (same coordinates as `DragInput::get_screen_pos`)
+    Press(Vec2),
 }
 
 /// This plugin only defines the common input interfaces between platforms.
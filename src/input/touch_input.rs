@@ -0,0 +1,33 @@
+use crate::prelude::*;
+
+/// Turns taps into presses, so menus work on touchscreens. Tapping with a second finger while one is already
+/// down is `Continue` instead (i.e. to skip cutscenes), since a plain tap already means `ConvoGoNext`.
+fn update_touch_non_game_input(
+    touches: Res<Touches>,
+    ideal_mult: Res<IdealMult>,
+    mut non_game_writer: EventWriter<NonGameInput>,
+    mut convo: EventWriter<ConvoGoNext>,
+) {
+    // Only one non-game input per frame, same as desktop
+    let Some(touch) = touches.iter_just_pressed().next() else {
+        return;
+    };
+    if touches.iter().count() > 1 {
+        non_game_writer.send(NonGameInput::Continue);
+        return;
+    }
+    let touch_pos = touch.position();
+    let screen_pos = Vec2::new(
+        touch_pos.x - IDEAL_WIDTH_f32 * ideal_mult.0 / 2.0,
+        -touch_pos.y + IDEAL_HEIGHT_f32 * ideal_mult.0 / 2.0,
+    ) / ideal_mult.0;
+    non_game_writer.send(NonGameInput::Press(screen_pos));
+    convo.send(ConvoGoNext);
+}
+
+pub struct TouchInputPlugin;
+impl Plugin for TouchInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_touch_non_game_input.in_set(InputSet));
+    }
+}
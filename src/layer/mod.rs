@@ -263,11 +263,16 @@ fn setup_layer_materials(
         .spawn((
             Name::new("menu_layer_quad"),
             SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(camera_targets.ideal_size.as_vec2()),
+                    ..default()
+                },
                 transform: tran_tran!(Vec3::Z * 3.0),
                 texture: MENU_MATERIAL,
                 ..default()
             },
             combined_layer.clone(),
+            ResizeQuad,
         ))
         .set_parent(root.eid());
 
@@ -294,8 +299,10 @@ fn setup_layer_cameras(
     layer_colors: Res<LayerColors>,
     layer_root: Res<LayerRoot>,
 ) {
+    // Game layers are in ideal pixels (drawn with extra detail), the menu layer is in menu pixels
+    let detail_scale = 1.0 / camera_targets.detail_growth as f32;
     macro_rules! spawn_layer_camera {
-        ($comp:ty, $name:expr, $order:expr, $image:expr, $clear_color:expr) => {{
+        ($comp:ty, $name:expr, $order:expr, $image:expr, $clear_color:expr, $scale:expr) => {{
             commands
                 .spawn((
                     Name::new($name),
@@ -309,7 +316,7 @@ fn setup_layer_cameras(
                         projection: OrthographicProjection {
                             near: ZIX_MIN,
                             far: ZIX_MAX,
-                            scale: 1.0 / camera_targets.detail_growth as f32,
+                            scale: $scale,
                             ..default()
                         },
                         ..Default::default()
@@ -325,35 +332,40 @@ fn setup_layer_cameras(
         "bg_light_camera",
         0,
         camera_targets.bg_light_target.clone(),
-        layer_colors.bg_ambient_light
+        layer_colors.bg_ambient_light,
+        detail_scale
     );
     spawn_layer_camera!(
         BgSpriteLayer,
         "bg_sprite_camera",
         1,
         camera_targets.bg_sprite_target.clone(),
-        layer_colors.bg_clear_color
+        layer_colors.bg_clear_color,
+        detail_scale
     );
     spawn_layer_camera!(
         LightLayer,
         "fg_light_camera",
         2,
         camera_targets.light_target.clone(),
-        layer_colors.ambient_light
+        layer_colors.ambient_light,
+        detail_scale
     );
     spawn_layer_camera!(
         SpriteLayer,
         "fg_sprite_camera",
         3,
         camera_targets.sprite_target.clone(),
-        layer_colors.clear_color
+        layer_colors.clear_color,
+        detail_scale
     );
     spawn_layer_camera!(
         MenuLayer,
         "menu_camera",
         4,
         camera_targets.menu_target.clone(),
-        layer_colors.menu_clear_color
+        layer_colors.menu_clear_color,
+        1.0
     );
 }
 
@@ -1,13 +1,54 @@
 //! I've found this kind of thing useful to have in the past.
 //! Basically you put a Birthing/Dying timer on entities.
 //! When the timer expires, there will be one pass of the schedule where this runs (Main)
-//! where `Birth` or `Death` is observable. After that, the component will be removed/entity despawned.
+//! where `Birth` or `Death` is observable. After that, the component will be removed/entity despawned
+//! (or parked, if it came from an `EntityPool`).
+//!
+//! If you'd rather react than poll, each entity also gets exactly one of each of these triggers:
+//! - `Born` when `Birthing` finishes
+//! - `StartedDying` when `Dying` is first added
+//! - `Died` when `Dying` finishes (right before the despawn)
+//! Use `commands.entity(eid).observe(...)` to hook into them.
+
+use bevy::ecs::{component::ComponentId, world::DeferredWorld};
 
 use crate::prelude::*;
 
+/// Why something is dying. Rides along on `Dying` so whoever observes the death can react to it
+/// (i.e. only drop loot when killed by the ship).
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq, Default)]
+pub enum DeathCause {
+    /// Ran out of time (animations finishing, projectiles expiring, etc.)
+    #[default]
+    Natural,
+    /// Took lethal damage. Source is the thing that dealt the final blow (if known).
+    Damage { source: Option<Entity> },
+    /// Killed itself (explosions and the like)
+    Suicide,
+    /// Removed because the content around it is going away (room teardown)
+    Cleanup,
+}
+
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct Birthing {
     birthspan: f32,
+    /// If true, physics (statics, triggers and movement) will ignore this entity until it is born
+    suppress_physics: bool,
+    /// Whether this birth is the one that put `PhysicsSuppressed` on (so it's the one that takes it off).
+    /// Something else that suppressed the entity first keeps its suppression after the birth.
+    owns_suppression: bool,
+}
+impl Birthing {
+    pub fn new(birthspan: f32) -> Self {
+        Self {
+            birthspan,
+            suppress_physics: false,
+            owns_suppression: false,
+        }
+    }
+
+    impl_get!(birthspan, f32);
+    impl_get_set_with!(suppress_physics, bool);
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
@@ -16,33 +57,92 @@ pub struct Birthed;
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct Dying {
     deathspan: f32,
+    cause: DeathCause,
 }
 impl Dying {
     pub fn new(deathspan: f32) -> Self {
-        Self { deathspan }
+        Self {
+            deathspan,
+            cause: default(),
+        }
     }
+
+    impl_get!(deathspan, f32);
+    impl_get_set_with!(cause, DeathCause);
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct Dead;
 
+/// Triggered on an entity once its `Birthing` has finished
+#[derive(Event, Debug, Clone)]
+pub struct Born;
+
+/// Triggered on an entity the first time `Dying` is added to it
+#[derive(Event, Debug, Clone)]
+pub struct StartedDying {
+    pub cause: DeathCause,
+}
+
+/// Triggered on an entity once its `Dying` has finished, right before it gets despawned
+#[derive(Event, Debug, Clone)]
+pub struct Died {
+    pub cause: DeathCause,
+}
+
+/// Suppresses physics the moment `Birthing` goes on (as part of the same command), so newborns never
+/// get a frame of physics before their birth starts
+fn on_add_birthing(mut world: DeferredWorld, eid: Entity, _: ComponentId) {
+    let already_suppressed = world.entity(eid).contains::<PhysicsSuppressed>();
+    let Some(mut birthing) = world.get_mut::<Birthing>(eid) else {
+        return;
+    };
+    if !birthing.suppress_physics || already_suppressed {
+        return;
+    }
+    birthing.owns_suppression = true;
+    world.commands().entity(eid).insert(PhysicsSuppressed);
+}
+
 fn update_final_states(
-    births: Query<Entity, With<Birthed>>,
-    deaths: Query<Entity, With<Dead>>,
+    births: Query<(Entity, &Birthing), With<Birthed>>,
+    deaths: Query<(Entity, Option<&Pooled>), With<Dead>>,
     mut commands: Commands,
 ) {
-    for eid in &births {
+    for (eid, birthing) in &births {
         commands.entity(eid).remove::<Birthing>();
         commands.entity(eid).remove::<Birthed>();
+        if birthing.owns_suppression {
+            commands.entity(eid).remove::<PhysicsSuppressed>();
+        }
     }
-    for eid in &deaths {
-        commands.entity(eid).despawn_recursive();
+    for (eid, pooled) in &deaths {
+        if pooled.is_some() {
+            // Pooled things go back to their pool to be reused
+            commands.entity(eid).remove::<(Dying, Dead)>().insert((
+                Parked,
+                PhysicsSuppressed,
+                Visibility::Hidden,
+            ));
+        } else {
+            commands.entity(eid).despawn_recursive();
+        }
+    }
+}
+
+/// Handles anything that just started dying
+fn start_transition_states(
+    new_dying: Query<(Entity, &Dying), Added<Dying>>,
+    mut commands: Commands,
+) {
+    for (eid, dying) in &new_dying {
+        commands.trigger_targets(StartedDying { cause: dying.cause }, eid);
     }
 }
 
 fn update_transition_states(
-    mut birthing: Query<(Entity, &mut Birthing)>,
-    mut dying: Query<(Entity, &mut Dying)>,
+    mut birthing: Query<(Entity, &mut Birthing), Without<Birthed>>,
+    mut dying: Query<(Entity, &mut Dying), Without<Dead>>,
     mut commands: Commands,
     bullet_time: Res<BulletTime>,
 ) {
@@ -50,12 +150,14 @@ fn update_transition_states(
         birthing.birthspan -= bullet_time.delta_seconds();
         if birthing.birthspan <= 0.0 {
             commands.entity(eid).insert(Birthed);
+            commands.trigger_targets(Born, eid);
         }
     }
     for (eid, mut dying) in &mut dying {
         dying.deathspan -= bullet_time.delta_seconds();
         if dying.deathspan <= 0.0 {
             commands.entity(eid).insert(Dead);
+            commands.trigger_targets(Died { cause: dying.cause }, eid);
         }
     }
 }
@@ -65,9 +167,17 @@ impl Plugin for LifecyclePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Dying>();
         app.register_type::<Birthing>();
+        app.register_type::<DeathCause>();
+        app.world_mut()
+            .register_component_hooks::<Birthing>()
+            .on_add(on_add_birthing);
         app.add_systems(
             PostUpdate,
-            (update_final_states, update_transition_states)
+            (
+                update_final_states,
+                start_transition_states,
+                update_transition_states,
+            )
                 .chain()
                 .after(AnimationSet)
                 .after(ParticlesSet),
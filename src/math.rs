@@ -1,7 +1,5 @@
 use std::f32::consts::PI;
 
-use rand::thread_rng;
-
 use crate::prelude::*;
 
 /// Given a point and a line segment, get the point on the line segment
@@ -283,7 +281,7 @@ pub fn triangulate(points: &[Vec2]) -> Vec<Triangle> {
     result
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
 pub enum Spleen {
     EaseInCubic,
     EaseOutCubic,
@@ -409,6 +407,6 @@ pub fn shortest_rotation(angle1: f32, angle2: f32) -> f32 {
 
 /// Nudges a z-index by a random amount so if two of it overlap it won't shutter
 /// Just a random number in [0, 1) reskin
-pub fn zix_nudge() -> f32 {
-    thread_rng().gen::<f32>()
+pub fn zix_nudge(rng: &mut impl Rng) -> f32 {
+    rng.gen::<f32>()
 }
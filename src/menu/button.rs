@@ -0,0 +1,166 @@
+//! Buttons for menus on the `MenuLayer`.
+//! Spawn them with `spawn_menu_button` and hook into `MenuButtonPressed` with `.observe(...)`.
+//! Focus moves between visible buttons (in `ix` order) with `NonGameInput::Up`/`Down`,
+//! `Continue` presses the focused one and `Press` presses whatever is under it.
+
+use bevy::ecs::system::EntityCommands;
+
+use crate::prelude::*;
+
+const BUTTON_SIZE: Vec2 = Vec2::new(360.0, 72.0);
+const BUTTON_FONT_SIZE: f32 = 40.0;
+const BUTTON_COLOR: Srgba = tailwind::GRAY_700;
+const BUTTON_FOCUSED_COLOR: Srgba = tailwind::SKY_600;
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct MenuButton {
+    /// Buttons are focused in increasing order of ix
+    ix: u32,
+    focused: bool,
+}
+impl MenuButton {
+    impl_get!(ix, u32);
+    impl_get!(focused, bool);
+}
+
+/// Triggered on a button when it gets pressed
+#[derive(Event, Debug, Clone)]
+pub struct MenuButtonPressed;
+
+#[derive(Bundle)]
+struct MenuButtonBundle {
+    name: Name,
+    button: MenuButton,
+    sprite: SpriteBundle,
+    render_layers: RenderLayers,
+}
+
+/// Spawns a button at a position (in menu coordinates) relative to `parent`
+pub fn spawn_menu_button<'a>(
+    commands: &'a mut Commands,
+    parent: Entity,
+    label: &str,
+    pos: Vec2,
+    ix: u32,
+) -> EntityCommands<'a> {
+    let mut ent_comm = commands.spawn(MenuButtonBundle {
+        name: Name::new(format!("button_{label}")),
+        button: MenuButton { ix, focused: false },
+        sprite: SpriteBundle {
+            sprite: Sprite {
+                custom_size: Some(BUTTON_SIZE),
+                color: BUTTON_COLOR.into(),
+                ..default()
+            },
+            transform: Transform::from_translation(pos.extend(0.0)),
+            ..default()
+        },
+        render_layers: MenuLayer::render_layers(),
+    });
+    ent_comm.set_parent(parent).with_children(|button| {
+        button.spawn((
+            Name::new("label"),
+            Text2dBundle {
+                text: Text::from_section(
+                    label,
+                    TextStyle {
+                        font_size: BUTTON_FONT_SIZE,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(Vec3::Z),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ));
+    });
+    ent_comm
+}
+
+fn update_menu_buttons(
+    mut input: EventReader<NonGameInput>,
+    mut buttons: Query<(
+        Entity,
+        &mut MenuButton,
+        &GlobalTransform,
+        &InheritedVisibility,
+    )>,
+    mut commands: Commands,
+) {
+    let mut visible: Vec<_> = buttons
+        .iter()
+        .filter(|(_, _, _, visibility)| visibility.get())
+        .map(|(eid, button, gtran, _)| {
+            (
+                eid,
+                button.ix,
+                button.focused,
+                gtran.translation().truncate(),
+            )
+        })
+        .collect();
+    if visible.is_empty() {
+        input.clear();
+        return;
+    }
+    visible.sort_by_key(|(_, ix, _, _)| *ix);
+    let mut focused_ix = visible
+        .iter()
+        .position(|(_, _, focused, _)| *focused)
+        .unwrap_or(0);
+    for event in input.read() {
+        match event {
+            NonGameInput::Up => {
+                focused_ix = (focused_ix + visible.len() - 1) % visible.len();
+            }
+            NonGameInput::Down => {
+                focused_ix = (focused_ix + 1) % visible.len();
+            }
+            NonGameInput::Continue => {
+                commands.trigger_targets(MenuButtonPressed, visible[focused_ix].0);
+            }
+            NonGameInput::Press(screen_pos) => {
+                let menu_pos = *screen_pos * MENU_GROWTH as f32;
+                let half_size = BUTTON_SIZE / 2.0;
+                if let Some(ix) = visible.iter().position(|(_, _, _, pos)| {
+                    (menu_pos.x - pos.x).abs() <= half_size.x
+                        && (menu_pos.y - pos.y).abs() <= half_size.y
+                }) {
+                    focused_ix = ix;
+                    commands.trigger_targets(MenuButtonPressed, visible[ix].0);
+                }
+            }
+            NonGameInput::Pause => (),
+        }
+    }
+    let focused_eid = visible[focused_ix].0;
+    for (eid, mut button, _, _) in &mut buttons {
+        let should_focus = eid == focused_eid;
+        if button.focused != should_focus {
+            button.focused = should_focus;
+        }
+    }
+}
+
+fn color_menu_buttons(mut buttons: Query<(&MenuButton, &mut Sprite), Changed<MenuButton>>) {
+    for (button, mut sprite) in &mut buttons {
+        sprite.color = if button.focused {
+            BUTTON_FOCUSED_COLOR.into()
+        } else {
+            BUTTON_COLOR.into()
+        };
+    }
+}
+
+pub(super) fn register_buttons(app: &mut App) {
+    app.register_type::<MenuButton>();
+
+    app.add_systems(
+        Update,
+        (update_menu_buttons, color_menu_buttons)
+            .chain()
+            .after(InputSet)
+            .in_set(MenuSet),
+    );
+}
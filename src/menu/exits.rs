@@ -0,0 +1,123 @@
+//! Once a room is cleared (and an upgrade picked), this shows where the run can go next
+//! (according to the progression graph). If the graph ends here, the run is over.
+
+use crate::prelude::*;
+
+/// Send this to show where the run can go from the current room
+#[derive(Event, Debug, Clone)]
+pub struct ShowRoomExits;
+
+/// The option a button leads to
+#[derive(Component, Debug, Clone, Reflect)]
+struct RoomExit(ProgressionOption);
+
+fn on_exit(
+    trigger: Trigger<MenuButtonPressed>,
+    exits: Query<&RoomExit>,
+    asset_server: Res<AssetServer>,
+    mut room_defs: ResMut<Assets<RoomDef>>,
+    gen_constants: Res<RoomGenConstants>,
+    mut rng: ResMut<GameRng>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut commands: Commands,
+) {
+    let Ok(RoomExit(option)) = exits.get(trigger.entity()) else {
+        return;
+    };
+    activate_room_template(
+        option,
+        None,
+        &asset_server,
+        &mut room_defs,
+        &gen_constants,
+        &mut rng,
+        &mut commands,
+    );
+    transitions.send(RequestTransition {
+        to: option.room_state.to_meta_state(),
+    });
+}
+
+fn on_finish(
+    _trigger: Trigger<MenuButtonPressed>,
+    mut saved_run: ResMut<SavedRun>,
+    mut writes: EventWriter<WriteSave>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    saved_run.clear();
+    writes.send(WriteSave);
+    transitions.send(RequestTransition {
+        to: MenuState::Title.to_meta_state(),
+    });
+}
+
+fn setup_exits(
+    mut show_exits: EventReader<ShowRoomExits>,
+    current_node: Res<CurrentNode>,
+    progression: Progression,
+    room_root: Res<RoomRoot>,
+    mut commands: Commands,
+) {
+    if show_exits.read().count() == 0 {
+        return;
+    }
+    let options = current_node
+        .get_id()
+        .map(|id| progression.next_options(id))
+        .unwrap_or_default();
+    let heading = if options.is_empty() {
+        "RUN COMPLETE"
+    } else {
+        "WHERE TO?"
+    };
+    let exits_eid = commands
+        .spawn((
+            Name::new("room_exits"),
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, -160.0, ZIX_MENU)),
+        ))
+        .set_parent(room_root.eid())
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("exits_heading"),
+                Text2dBundle {
+                    text: Text::from_section(
+                        heading,
+                        TextStyle {
+                            font_size: 48.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, 80.0, 0.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+        })
+        .id();
+    if options.is_empty() {
+        spawn_menu_button(&mut commands, exits_eid, "Finish", Vec2::ZERO, 0).observe(on_finish);
+        return;
+    }
+    // Side by side, centered
+    let spacing = 400.0;
+    let left = -spacing * (options.len() - 1) as f32 / 2.0;
+    for (ix, option) in options.into_iter().enumerate() {
+        let pos = Vec2::new(left + spacing * ix as f32, 0.0);
+        spawn_menu_button(&mut commands, exits_eid, &option.label, pos, ix as u32)
+            .insert(RoomExit(option))
+            .observe(on_exit);
+    }
+}
+
+pub(super) fn register_exits(app: &mut App) {
+    app.register_type::<RoomExit>();
+    app.add_event::<ShowRoomExits>();
+
+    app.add_systems(
+        Update,
+        setup_exits
+            .in_set(MenuSet)
+            .run_if(in_state(EncounterProgress::Meandering)),
+    );
+}
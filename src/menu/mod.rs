@@ -0,0 +1,32 @@
+//! Menus and other non-gameplay screens that live on the `MenuLayer`
+
+use crate::prelude::*;
+
+pub mod button;
+pub mod exits;
+pub mod pause;
+pub mod retry;
+pub mod settings;
+pub mod studio;
+pub mod title;
+pub mod upgrades;
+
+pub use button::*;
+pub use exits::ShowRoomExits;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MenuSet;
+
+pub(super) struct MenuPlugin;
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        button::register_buttons(app);
+        exits::register_exits(app);
+        pause::register_pause(app);
+        retry::register_retry(app);
+        settings::register_settings(app);
+        studio::register_studio(app);
+        title::register_title(app);
+        upgrades::register_upgrades(app);
+    }
+}
@@ -0,0 +1,216 @@
+//! Pausing. Can be triggered by input (`NonGameInput::Pause`), the window losing focus, or the app
+//! getting suspended (i.e. backgrounded on mobile).
+//! While paused, in-game time is frozen (`BulletTime`), audio is ducked, and there's an overlay
+//! on `PauseRoot` with options to resume, restart the current room, or quit.
+
+use bevy::window::{AppLifecycle, WindowFocused};
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct PauseConstants {
+    /// Multiplies audio volume while paused
+    duck_factor: f32,
+    /// How dark the overlay makes the game
+    dim_alpha: f32,
+}
+impl Default for PauseConstants {
+    fn default() -> Self {
+        Self {
+            duck_factor: 0.3,
+            dim_alpha: 0.6,
+        }
+    }
+}
+
+/// Only gameplay can be paused, and never in the middle of a transition
+fn can_pause(meta_state: &MetaState, transition_state: &MetaTransitionState) -> bool {
+    matches!(meta_state, MetaState::Room(_) | MetaState::Tutorial(_))
+        && matches!(transition_state, MetaTransitionState::Stable)
+}
+
+fn watch_pause_triggers(
+    mut non_game_input: EventReader<NonGameInput>,
+    mut focus_events: EventReader<WindowFocused>,
+    mut lifecycle_events: EventReader<AppLifecycle>,
+    meta_state: Res<State<MetaState>>,
+    transition_state: Res<State<MetaTransitionState>>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    let toggled = non_game_input
+        .read()
+        .filter(|event| matches!(event, NonGameInput::Pause))
+        .count()
+        % 2
+        == 1;
+    let lost_focus = focus_events.read().any(|event| !event.focused);
+    let suspended = lifecycle_events
+        .read()
+        .any(|event| matches!(event, AppLifecycle::WillSuspend | AppLifecycle::Suspended));
+    match pause_state.get() {
+        PauseState::Unpaused => {
+            if (toggled || lost_focus || suspended)
+                && can_pause(meta_state.get(), transition_state.get())
+            {
+                next_pause_state.set(PauseState::Paused);
+            }
+        }
+        PauseState::Paused => {
+            // Coming back into focus should NOT unpause, the player should get a moment to get ready
+            if toggled {
+                next_pause_state.set(PauseState::Unpaused);
+            }
+        }
+    }
+}
+
+/// If something else moves us out of gameplay (i.e. a transition), stop being paused
+fn unpause_outside_gameplay(
+    meta_state: Res<State<MetaState>>,
+    transition_state: Res<State<MetaTransitionState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    if !can_pause(meta_state.get(), transition_state.get()) {
+        next_pause_state.set(PauseState::Unpaused);
+    }
+}
+
+fn on_resume(
+    _trigger: Trigger<MenuButtonPressed>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    next_pause_state.set(PauseState::Unpaused);
+}
+
+fn on_restart(
+    _trigger: Trigger<MenuButtonPressed>,
+    meta_state: Res<State<MetaState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    let to = match meta_state.get_room_state() {
+        Some(room_state) => room_state
+            .with_progress(EncounterProgress::Entering)
+            .to_meta_state(),
+        None => *meta_state.get(),
+    };
+    next_pause_state.set(PauseState::Unpaused);
+    transitions.send(RequestTransition { to });
+}
+
+fn on_quit(_trigger: Trigger<MenuButtonPressed>, mut exits: EventWriter<AppExit>) {
+    exits.send(AppExit::Success);
+}
+
+fn setup_pause(
+    mut bullet_time: ResMut<BulletTime>,
+    constants: Res<PauseConstants>,
+    proot: Res<PauseRoot>,
+    mut commands: Commands,
+) {
+    bullet_time.pause();
+    commands
+        .spawn((
+            Name::new("pause_dim"),
+            SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(MENU_VEC_f32),
+                    color: Color::BLACK.with_alpha(constants.dim_alpha),
+                    ..default()
+                },
+                // Behind the title and buttons
+                transform: Transform::from_xyz(0.0, 0.0, -1.0),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ))
+        .set_parent(proot.eid());
+    commands
+        .spawn((
+            Name::new("pause_title"),
+            Text2dBundle {
+                text: Text::from_section(
+                    "PAUSED",
+                    TextStyle {
+                        font_size: 72.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, 160.0, 1.0),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ))
+        .set_parent(proot.eid());
+    spawn_menu_button(
+        &mut commands,
+        proot.eid(),
+        "Resume",
+        Vec2::new(0.0, 40.0),
+        0,
+    )
+    .observe(on_resume);
+    spawn_menu_button(
+        &mut commands,
+        proot.eid(),
+        "Restart",
+        Vec2::new(0.0, -60.0),
+        1,
+    )
+    .observe(on_restart);
+    spawn_menu_button(
+        &mut commands,
+        proot.eid(),
+        "Quit",
+        Vec2::new(0.0, -160.0),
+        2,
+    )
+    .observe(on_quit);
+}
+
+fn teardown_pause(
+    mut bullet_time: ResMut<BulletTime>,
+    proot: Res<PauseRoot>,
+    mut commands: Commands,
+) {
+    bullet_time.unpause();
+    commands.entity(proot.eid()).despawn_descendants();
+}
+
+/// Keeps all playing audio at its volume from the settings (music or effects), ducked if paused
+fn update_audio_volume(
+    pause_state: Res<State<PauseState>>,
+    settings: Res<GameSettings>,
+    constants: Res<PauseConstants>,
+    sinks: Query<(&AudioSink, Option<&AudioKind>)>,
+) {
+    let duck = match pause_state.get() {
+        PauseState::Unpaused => 1.0,
+        PauseState::Paused => constants.duck_factor,
+    };
+    for (sink, kind) in &sinks {
+        let volume = settings.volume_for(kind.copied().unwrap_or_default()) * duck;
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+pub(super) fn register_pause(app: &mut App) {
+    app.insert_resource(PauseConstants::default());
+    debug_resource!(app, PauseConstants);
+
+    app.add_systems(OnEnter(PauseState::Paused), setup_pause);
+    app.add_systems(OnExit(PauseState::Paused), teardown_pause);
+    app.add_systems(
+        Update,
+        (
+            watch_pause_triggers.after(InputSet),
+            unpause_outside_gameplay.run_if(in_state(PauseState::Paused)),
+            update_audio_volume,
+        )
+            .in_set(MenuSet),
+    );
+}
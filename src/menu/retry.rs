@@ -0,0 +1,130 @@
+//! The screen that shows up after the ship dies. Retry restarts the same room from scratch,
+//! or you can give up and go back to the title.
+
+use crate::prelude::*;
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct RetryStats;
+
+fn on_retry(
+    _trigger: Trigger<MenuButtonPressed>,
+    room_state: Res<State<RoomState>>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    transitions.send(RequestTransition {
+        to: room_state
+            .get()
+            .with_progress(EncounterProgress::Entering)
+            .to_meta_state(),
+    });
+}
+
+fn on_give_up(
+    _trigger: Trigger<MenuButtonPressed>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    transitions.send(RequestTransition {
+        to: MenuState::Title.to_meta_state(),
+    });
+}
+
+fn setup_retry(room_root: Res<RoomRoot>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("retry_dim"),
+            SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(MENU_VEC_f32),
+                    color: Color::BLACK.with_alpha(0.6),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 0.0, ZIX_MENU),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ))
+        .set_parent(room_root.eid())
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("retry_title"),
+                Text2dBundle {
+                    text: Text::from_section(
+                        "WRECKED",
+                        TextStyle {
+                            font_size: 72.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, 200.0, 1.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+            parent.spawn((
+                Name::new("retry_stats"),
+                RetryStats,
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 36.0,
+                            color: tailwind::GRAY_300.into(),
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, 120.0, 1.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+        });
+    // Buttons go on top of the dim
+    let buttons_eid = commands
+        .spawn((
+            Name::new("retry_buttons"),
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, ZIX_MENU + 2.0)),
+        ))
+        .set_parent(room_root.eid())
+        .id();
+    spawn_menu_button(&mut commands, buttons_eid, "Retry", Vec2::ZERO, 0).observe(on_retry);
+    spawn_menu_button(
+        &mut commands,
+        buttons_eid,
+        "Give up",
+        Vec2::new(0.0, -100.0),
+        1,
+    )
+    .observe(on_give_up);
+}
+
+/// The death gets recorded in the same frame this screen shows up, so keep the text in sync
+fn update_retry_stats(
+    saved_run: Res<SavedRun>,
+    current_node: Res<CurrentNode>,
+    mut texts: Query<&mut Text, With<RetryStats>>,
+) {
+    let stats = saved_run.get_stats();
+    let label = format!(
+        "Deaths here: {}    This run: {}",
+        stats.deaths_in(current_node.get_id()),
+        stats.total_deaths()
+    );
+    for mut text in &mut texts {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
+
+pub(super) fn register_retry(app: &mut App) {
+    app.register_type::<RetryStats>();
+
+    app.add_systems(OnEnter(EncounterProgress::Dead), setup_retry);
+    app.add_systems(
+        Update,
+        update_retry_stats
+            .in_set(MenuSet)
+            .run_if(in_state(EncounterProgress::Dead)),
+    );
+}
@@ -0,0 +1,121 @@
+//! The settings screen. Changes are saved automatically (see `save.rs`).
+
+use crate::prelude::*;
+
+/// Each press turns the volume down by this much, wrapping back around to full
+const VOLUME_STEP: f32 = 0.25;
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+enum SettingsOption {
+    Music,
+    Effects,
+}
+impl SettingsOption {
+    fn label(&self, settings: &GameSettings) -> String {
+        match self {
+            Self::Music => format!("Music: {:.0}%", settings.music_volume * 100.0),
+            Self::Effects => format!("Effects: {:.0}%", settings.effects_volume * 100.0),
+        }
+    }
+}
+
+fn step_volume(volume: f32) -> f32 {
+    if volume <= 0.0 {
+        1.0
+    } else {
+        (volume - VOLUME_STEP).max(0.0)
+    }
+}
+
+fn on_option(
+    trigger: Trigger<MenuButtonPressed>,
+    options: Query<&SettingsOption>,
+    mut settings: ResMut<GameSettings>,
+) {
+    let Ok(option) = options.get(trigger.entity()) else {
+        return;
+    };
+    match option {
+        SettingsOption::Music => settings.music_volume = step_volume(settings.music_volume),
+        SettingsOption::Effects => settings.effects_volume = step_volume(settings.effects_volume),
+    }
+}
+
+fn on_back(_trigger: Trigger<MenuButtonPressed>, mut transitions: EventWriter<RequestTransition>) {
+    transitions.send(RequestTransition {
+        to: MenuState::Title.to_meta_state(),
+    });
+}
+
+fn setup_settings(settings: Res<GameSettings>, mroot: Res<MenuRoot>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("settings_title"),
+            Text2dBundle {
+                text: Text::from_section(
+                    "SETTINGS",
+                    TextStyle {
+                        font_size: 72.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, 200.0, 0.0),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ))
+        .set_parent(mroot.eid());
+    for (ix, option) in [SettingsOption::Music, SettingsOption::Effects]
+        .into_iter()
+        .enumerate()
+    {
+        let pos = Vec2::new(0.0, 40.0 - 100.0 * ix as f32);
+        spawn_menu_button(
+            &mut commands,
+            mroot.eid(),
+            &option.label(&settings),
+            pos,
+            ix as u32,
+        )
+        .insert(option)
+        .observe(on_option);
+    }
+    spawn_menu_button(
+        &mut commands,
+        mroot.eid(),
+        "Back",
+        Vec2::new(0.0, -160.0),
+        2,
+    )
+    .observe(on_back);
+}
+
+fn update_settings_labels(
+    settings: Res<GameSettings>,
+    options: Query<(&SettingsOption, &Children)>,
+    mut texts: Query<&mut Text>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (option, children) in &options {
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = option.label(&settings);
+            }
+        }
+    }
+}
+
+pub(super) fn register_settings(app: &mut App) {
+    app.register_type::<SettingsOption>();
+
+    app.add_systems(OnEnter(MenuState::Settings.to_meta_state()), setup_settings);
+    app.add_systems(
+        Update,
+        update_settings_labels
+            .in_set(MenuSet)
+            .run_if(in_state(MenuState::Settings.to_meta_state())),
+    );
+}
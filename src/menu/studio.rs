@@ -0,0 +1,101 @@
+//! The studio splash that shows when the game boots
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct StudioConstants {
+    fade_in_time: f32,
+    /// How long the splash sticks around (after fading in) before moving on by itself
+    hold_time: f32,
+    scale: f32,
+}
+impl Default for StudioConstants {
+    fn default() -> Self {
+        Self {
+            fade_in_time: 0.6,
+            hold_time: 2.0,
+            scale: 1.5,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct StudioSplash {
+    time: f32,
+}
+
+#[derive(Bundle)]
+struct StudioSplashBundle {
+    name: Name,
+    splash: StudioSplash,
+    sprite: SpriteBundle,
+    render_layers: RenderLayers,
+}
+impl StudioSplashBundle {
+    fn new(texture: Handle<Image>, scale: f32) -> Self {
+        Self {
+            name: Name::new("studio_splash"),
+            splash: StudioSplash { time: 0.0 },
+            sprite: SpriteBundle {
+                texture,
+                sprite: Sprite {
+                    color: Color::WHITE.with_alpha(0.0),
+                    ..default()
+                },
+                transform: Transform::from_scale(Vec3::new(scale, scale, 1.0)),
+                ..default()
+            },
+            render_layers: MenuLayer::render_layers(),
+        }
+    }
+}
+
+/// NOTE: This is spawned here instead of `OnEnter` because studio is the initial state,
+/// which is entered before the roots exist
+fn update_studio(
+    mut splash_q: Query<(&mut StudioSplash, &mut Sprite)>,
+    mut input: EventReader<NonGameInput>,
+    mut transitions: EventWriter<RequestTransition>,
+    constants: Res<StudioConstants>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mroot: Res<MenuRoot>,
+    mut commands: Commands,
+) {
+    let Ok((mut splash, mut sprite)) = splash_q.get_single_mut() else {
+        commands
+            .spawn(StudioSplashBundle::new(
+                asset_server.load("play/dream-lake.png"),
+                constants.scale,
+            ))
+            .set_parent(mroot.eid());
+        input.clear();
+        return;
+    };
+    splash.time += time.delta_seconds();
+    let frac = (splash.time / constants.fade_in_time).min(1.0);
+    sprite.color.set_alpha(Spleen::EaseInOutQuad.interp(frac));
+    let skipped = input
+        .read()
+        .any(|event| matches!(event, NonGameInput::Continue | NonGameInput::Press(_)));
+    if skipped || splash.time >= constants.fade_in_time + constants.hold_time {
+        transitions.send(RequestTransition {
+            to: MenuState::Title.to_meta_state(),
+        });
+    }
+}
+
+pub(super) fn register_studio(app: &mut App) {
+    app.register_type::<StudioSplash>();
+    app.insert_resource(StudioConstants::default());
+    debug_resource!(app, StudioConstants);
+
+    app.add_systems(
+        Update,
+        update_studio
+            .after(InputSet)
+            .in_set(MenuSet)
+            .run_if(in_state(MenuState::Studio.to_meta_state()))
+            .run_if(in_state(TransitionState::Stable)),
+    );
+}
@@ -0,0 +1,154 @@
+//! The title screen. Start a new run, continue the saved one, change settings or quit.
+
+use crate::prelude::*;
+
+/// Where a new run starts if the progression graph failed to load (or is broken)
+const FIRST_ROOM_FILE: &str = "rooms/debug.room.ron";
+
+/// The run that was asked for on the title screen. It can't start until the progression graph is done
+/// loading, since that's what knows the rooms.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum PendingRun {
+    #[default]
+    None,
+    New,
+    Continue,
+}
+
+fn on_start(_trigger: Trigger<MenuButtonPressed>, mut pending: ResMut<PendingRun>) {
+    *pending = PendingRun::New;
+}
+
+fn on_continue(_trigger: Trigger<MenuButtonPressed>, mut pending: ResMut<PendingRun>) {
+    *pending = PendingRun::Continue;
+}
+
+fn start_pending_run(
+    mut pending: ResMut<PendingRun>,
+    mut saved_run: ResMut<SavedRun>,
+    progression: Progression,
+    asset_server: Res<AssetServer>,
+    mut room_defs: ResMut<Assets<RoomDef>>,
+    gen_constants: Res<RoomGenConstants>,
+    mut rng: ResMut<GameRng>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut commands: Commands,
+) {
+    if progression.is_loading() {
+        return;
+    }
+    let (option, room_seed, fallback_file, to) = match *pending {
+        PendingRun::None => return,
+        PendingRun::New => {
+            saved_run.clear();
+            (
+                progression.start_option(),
+                None,
+                FIRST_ROOM_FILE.to_string(),
+                CutsceneState::Intro.to_meta_state(),
+            )
+        }
+        PendingRun::Continue => {
+            let Some(run) = saved_run.get_run() else {
+                *pending = PendingRun::None;
+                return;
+            };
+            // The graph knows what room goes with the node (generated rooms come out the same from the saved seed)
+            (
+                progression.saved_option(run),
+                run.room_seed,
+                run.room_file.clone().unwrap_or(FIRST_ROOM_FILE.to_string()),
+                run.room_state().to_meta_state(),
+            )
+        }
+    };
+    *pending = PendingRun::None;
+    match option {
+        Some(option) => activate_room_template(
+            &option,
+            room_seed,
+            &asset_server,
+            &mut room_defs,
+            &gen_constants,
+            &mut rng,
+            &mut commands,
+        ),
+        None => {
+            commands.insert_resource(ActiveRoomFile::new(asset_server.load(fallback_file)));
+            commands.insert_resource(CurrentNode::default());
+        }
+    }
+    transitions.send(RequestTransition { to });
+}
+
+fn on_settings(
+    _trigger: Trigger<MenuButtonPressed>,
+    mut transitions: EventWriter<RequestTransition>,
+) {
+    transitions.send(RequestTransition {
+        to: MenuState::Settings.to_meta_state(),
+    });
+}
+
+fn on_quit(_trigger: Trigger<MenuButtonPressed>, mut exits: EventWriter<AppExit>) {
+    exits.send(AppExit::Success);
+}
+
+fn setup_title(saved_run: Res<SavedRun>, mroot: Res<MenuRoot>, mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("title"),
+            Text2dBundle {
+                text: Text::from_section(
+                    "ZENITH",
+                    TextStyle {
+                        font_size: 120.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, 200.0, 0.0),
+                ..default()
+            },
+            MenuLayer::render_layers(),
+        ))
+        .set_parent(mroot.eid());
+    // Continue only shows up if there's something to continue
+    let mut y = 40.0;
+    let mut ix = 0;
+    let mut next_slot = || {
+        let slot = (Vec2::new(0.0, y), ix);
+        y -= 100.0;
+        ix += 1;
+        slot
+    };
+    let (pos, ix) = next_slot();
+    spawn_menu_button(&mut commands, mroot.eid(), "Start", pos, ix).observe(on_start);
+    if saved_run.can_continue() {
+        let (pos, ix) = next_slot();
+        spawn_menu_button(&mut commands, mroot.eid(), "Continue", pos, ix).observe(on_continue);
+    }
+    let (pos, ix) = next_slot();
+    spawn_menu_button(&mut commands, mroot.eid(), "Settings", pos, ix).observe(on_settings);
+    let (pos, ix) = next_slot();
+    spawn_menu_button(&mut commands, mroot.eid(), "Quit", pos, ix).observe(on_quit);
+}
+
+fn reset_pending_run(mut pending: ResMut<PendingRun>) {
+    *pending = PendingRun::None;
+}
+
+pub(super) fn register_title(app: &mut App) {
+    app.insert_resource(PendingRun::default());
+
+    app.add_systems(
+        OnEnter(MenuState::Title.to_meta_state()),
+        (reset_pending_run, setup_title),
+    );
+    app.add_systems(
+        Update,
+        start_pending_run
+            .in_set(MenuSet)
+            .run_if(in_state(MenuState::Title.to_meta_state())),
+    );
+}
@@ -0,0 +1,136 @@
+//! After clearing a room, pick one of a few random upgrades (see `ship/upgrades.rs`).
+//! Once one is picked (or if there's nothing to offer) the room exits show up.
+
+use bevy::{sprite::Anchor, text::Text2dBounds};
+use rand::seq::SliceRandom;
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct UpgradeMenuConstants {
+    /// How many upgrades to choose between (fewer if the pool is small)
+    num_offered: usize,
+}
+impl Default for UpgradeMenuConstants {
+    fn default() -> Self {
+        Self { num_offered: 3 }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct UpgradeChoices;
+
+/// The upgrade a button takes
+#[derive(Component, Debug, Clone, Reflect)]
+struct UpgradeChoice(String);
+
+fn on_pick(
+    trigger: Trigger<MenuButtonPressed>,
+    choices: Query<&UpgradeChoice>,
+    containers: Query<Entity, With<UpgradeChoices>>,
+    mut saved_run: ResMut<SavedRun>,
+    mut writes: EventWriter<WriteSave>,
+    mut show_exits: EventWriter<ShowRoomExits>,
+    mut commands: Commands,
+) {
+    let Ok(UpgradeChoice(id)) = choices.get(trigger.entity()) else {
+        return;
+    };
+    saved_run.add_upgrade(id);
+    writes.send(WriteSave);
+    for eid in &containers {
+        commands.entity(eid).despawn_recursive();
+    }
+    show_exits.send(ShowRoomExits);
+}
+
+fn offer_upgrades(
+    saved_run: Res<SavedRun>,
+    pool: Res<UpgradePool>,
+    pool_defs: Res<Assets<UpgradePoolDef>>,
+    constants: Res<UpgradeMenuConstants>,
+    mut rng: ResMut<GameRng>,
+    room_root: Res<RoomRoot>,
+    mut show_exits: EventWriter<ShowRoomExits>,
+    mut commands: Commands,
+) {
+    let offered: Vec<UpgradeDef> = match pool_defs.get(pool.get_handle()) {
+        // Upgrades only stick around if there's a run to save them in
+        Some(pool_def) if saved_run.can_continue() => pool_def
+            .upgrades
+            .choose_multiple(rng.stream(RngStream::Environment), constants.num_offered)
+            .cloned()
+            .collect(),
+        _ => vec![],
+    };
+    if offered.is_empty() {
+        show_exits.send(ShowRoomExits);
+        return;
+    }
+    let container_eid = commands
+        .spawn((
+            Name::new("upgrade_choices"),
+            UpgradeChoices,
+            SpatialBundle::from_transform(Transform::from_xyz(0.0, -120.0, ZIX_MENU)),
+        ))
+        .set_parent(room_root.eid())
+        .with_children(|parent| {
+            parent.spawn((
+                Name::new("upgrades_heading"),
+                Text2dBundle {
+                    text: Text::from_section(
+                        "PICK AN UPGRADE",
+                        TextStyle {
+                            font_size: 48.0,
+                            color: Color::WHITE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_xyz(0.0, 80.0, 0.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ));
+        })
+        .id();
+    let spacing = 400.0;
+    let left = -spacing * (offered.len() - 1) as f32 / 2.0;
+    for (ix, upgrade) in offered.into_iter().enumerate() {
+        let pos = Vec2::new(left + spacing * ix as f32, 0.0);
+        spawn_menu_button(&mut commands, container_eid, &upgrade.name, pos, ix as u32)
+            .insert(UpgradeChoice(upgrade.id.clone()))
+            .observe(on_pick);
+        commands
+            .spawn((
+                Name::new("upgrade_description"),
+                Text2dBundle {
+                    text: Text::from_section(
+                        upgrade.description,
+                        TextStyle {
+                            font_size: 24.0,
+                            color: tailwind::GRAY_300.into(),
+                            ..default()
+                        },
+                    )
+                    .with_justify(JustifyText::Center),
+                    text_anchor: Anchor::TopCenter,
+                    text_2d_bounds: Text2dBounds {
+                        size: Vec2::new(spacing - 40.0, f32::INFINITY),
+                    },
+                    transform: Transform::from_xyz(pos.x, pos.y - 50.0, 0.0),
+                    ..default()
+                },
+                MenuLayer::render_layers(),
+            ))
+            .set_parent(container_eid);
+    }
+}
+
+pub(super) fn register_upgrades(app: &mut App) {
+    app.register_type::<UpgradeChoices>();
+    app.register_type::<UpgradeChoice>();
+    app.insert_resource(UpgradeMenuConstants::default());
+    debug_resource!(app, UpgradeMenuConstants);
+
+    app.add_systems(OnEnter(EncounterProgress::Meandering), offer_upgrades);
+}
@@ -12,6 +12,10 @@ impl MirageAnimationManager {
             offsets: room_state.mirage_offsets(),
         }
     }
+
+    fn rewrap(&mut self, room_state: &RoomState) {
+        self.offsets = room_state.mirage_offsets();
+    }
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
@@ -24,6 +28,10 @@ impl MirageTextureManager {
             offsets: room_state.mirage_offsets(),
         }
     }
+
+    fn rewrap(&mut self, room_state: &RoomState) {
+        self.offsets = room_state.mirage_offsets();
+    }
 }
 
 #[derive(Component, Debug, Clone, Reflect)]
@@ -46,7 +54,10 @@ pub fn spawn_animation_manager_mirages<StateMachine: AnimationStateMachine>(
     mut commands: Commands,
     managers: Query<
         (Entity, &Children, &MirageAnimationManager),
-        (Changed<AnimationManager<StateMachine>>,),
+        Or<(
+            Changed<AnimationManager<StateMachine>>,
+            Changed<MirageAnimationManager>,
+        )>,
     >,
     ditto_q: Query<(
         Entity,
@@ -58,7 +69,10 @@ pub fn spawn_animation_manager_mirages<StateMachine: AnimationStateMachine>(
 ) {
     for (_manager_eid, children, mirage) in &managers {
         for (ix, child) in children.iter().enumerate() {
-            let (ditto_eid, mesh, mat, render_layers) = ditto_q.get(*child).unwrap();
+            // Not every child is a body (i.e. rig parts, which have their own mirages)
+            let Ok((ditto_eid, mesh, mat, render_layers)) = ditto_q.get(*child) else {
+                continue;
+            };
             for (jx, offset) in mirage.offsets.iter().enumerate() {
                 commands
                     .spawn(MirageMeshMatBundle {
@@ -82,7 +96,10 @@ pub fn spawn_texture_manager_mirages<StateMachine: TextureStateMachine>(
     mut commands: Commands,
     managers: Query<
         (Entity, &MirageTextureManager, &Children),
-        (Changed<TextureManager<StateMachine>>,),
+        Or<(
+            Changed<TextureManager<StateMachine>>,
+            Changed<MirageTextureManager>,
+        )>,
     >,
     ditto_q: Query<(
         Entity,
@@ -139,9 +156,37 @@ fn update_mirage_mesh_mats(
     }
 }
 
+/// When the room changes size, all the mirages are in the wrong place. Throw them out and
+/// update the offsets, which will make the managers spawn new ones.
+fn rewrap_mirages(
+    meta_state: Res<State<MetaState>>,
+    mut last_size: Local<Option<UVec2>>,
+    mut anim_mirages: Query<&mut MirageAnimationManager>,
+    mut texture_mirages: Query<&mut MirageTextureManager>,
+    root: Res<MirageRoot>,
+    mut commands: Commands,
+) {
+    let Some(room_state) = meta_state.get_room_state() else {
+        return;
+    };
+    let old_size = last_size.replace(room_state.room_size);
+    if old_size.is_none() || old_size == Some(room_state.room_size) {
+        return;
+    }
+    commands.entity(root.eid()).despawn_descendants();
+    for mut mirage in &mut anim_mirages {
+        mirage.rewrap(&room_state);
+    }
+    for mut mirage in &mut texture_mirages {
+        mirage.rewrap(&room_state);
+    }
+}
+
 pub(super) fn register_mirage_drawing(app: &mut App) {
     app.register_type::<MirageMeshMat>();
 
+    app.add_systems(Update, rewrap_mirages.before(AnimationSet));
+
     app.add_systems(
         PostUpdate,
         update_mirage_mesh_mats
@@ -1 +1,29 @@
+use crate::prelude::*;
 
+/// Wrapped bounds have their duplicates baked in at spawn. When the room changes size, rebuild them.
+fn rewrap_bounds(
+    meta_state: Res<State<MetaState>>,
+    mut last_size: Local<Option<UVec2>>,
+    mut static_txs: Query<(&GlobalTransform, &mut StaticTx)>,
+    mut trigger_txs: Query<(&GlobalTransform, &mut TriggerTx)>,
+) {
+    let Some(room_state) = meta_state.get_room_state() else {
+        return;
+    };
+    let old_size = last_size.replace(room_state.room_size);
+    if old_size.is_none() || old_size == Some(room_state.room_size) {
+        return;
+    }
+    for (gtran, mut tx) in &mut static_txs {
+        tx.bounds.rewrap(&room_state);
+        tx.bounds.refresh_placement(gtran.pos_n_angle());
+    }
+    for (gtran, mut tx) in &mut trigger_txs {
+        tx.bounds.rewrap(&room_state);
+        tx.bounds.refresh_placement(gtran.pos_n_angle());
+    }
+}
+
+pub(super) fn register_mirage_physics(app: &mut App) {
+    app.add_systems(Update, rewrap_bounds.before(PhysicsSet));
+}
@@ -16,5 +16,6 @@ impl Plugin for MiragePlugin {
         app.register_type::<MirageTextureManager>();
 
         mirage_drawing::register_mirage_drawing(app);
+        mirage_physics::register_mirage_physics(app);
     }
 }
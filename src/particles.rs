@@ -73,6 +73,18 @@ impl Particle {
     }
 }
 
+/// Particles waiting to be materialized. Spawners push here instead of spawning a `Particle`
+/// entity so that fast-moving things don't create (and then immediately despawn) tons of entities.
+#[derive(Resource, Default)]
+pub struct PendingParticles {
+    particles: Vec<Particle>,
+}
+impl PendingParticles {
+    pub fn push(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
+}
+
 /// A particle spawner that should be attached to things with DynoTrans AND either a static or trigger receiver.
 /// This will make it so that during physics, it will spawn particles every "unit" it travels
 /// This makes it so a fast-travelling thing can still create a smooth streak
@@ -99,12 +111,10 @@ impl DynoAwareParticleSpawner {
         self
     }
 
-    pub fn do_spawn(&self, base_pos: Vec2, commands: &mut Commands, proot: &ParticlesRoot) {
+    pub fn do_spawn(&self, base_pos: Vec2, pending: &mut PendingParticles) {
         for offset in &self.poses {
             for reference in &self.references {
-                commands
-                    .spawn(reference.clone().with_pos(base_pos + *offset))
-                    .set_parent(proot.eid());
+                pending.push(reference.clone().with_pos(base_pos + *offset));
             }
         }
     }
@@ -135,12 +145,10 @@ impl SimpleParticleSpawner {
         self
     }
 
-    pub fn do_spawn(&self, base_pos: Vec2, commands: &mut Commands, proot: &ParticlesRoot) {
+    pub fn do_spawn(&self, base_pos: Vec2, pending: &mut PendingParticles) {
         for offset in &self.poses {
             for reference in &self.references {
-                commands
-                    .spawn(reference.clone().with_pos(base_pos + *offset))
-                    .set_parent(proot.eid());
+                pending.push(reference.clone().with_pos(base_pos + *offset));
             }
         }
     }
@@ -175,23 +183,35 @@ struct ParticleInternalBundle {
     render_layers: RenderLayers,
 }
 impl ParticleInternalBundle {
-    fn spawn(pos: Vec2, particle: &Particle, parent: Entity, commands: &mut Commands) {
-        let mut ent_comm = commands.spawn(Self {
-            particle: particle.internal.clone(),
-            sprite: SpriteBundle {
-                sprite: Sprite {
-                    custom_size: Some(Vec2::ZERO),
+    fn spawn(
+        pos: Vec2,
+        particle: &Particle,
+        parent: Entity,
+        pool: &mut EntityPool<ParticleInternal>,
+        commands: &mut Commands,
+    ) {
+        let Some(eid) = pool.acquire(commands, Some(parent)) else {
+            // Pool is full and doesn't want to give us anything, no particle for you
+            return;
+        };
+        // NOTE: Always insert the DynoTran (even if zero) so recycled particles don't keep old velocities
+        commands.entity(eid).insert((
+            Self {
+                particle: particle.internal.clone(),
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::ZERO),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(pos.extend(0.0)),
                     ..default()
                 },
-                transform: Transform::from_translation(pos.extend(0.0)),
-                ..default()
+                render_layers: SpriteLayer::render_layers(),
             },
-            render_layers: SpriteLayer::render_layers(),
-        });
-        ent_comm.set_parent(parent);
-        if let Some(vel) = particle.vel {
-            ent_comm.insert(DynoTran { vel });
-        }
+            DynoTran {
+                vel: particle.vel.unwrap_or_default(),
+            },
+        ));
     }
 }
 
@@ -199,23 +219,50 @@ fn materialize_particles(
     mut commands: Commands,
     proot: Res<ParticlesRoot>,
     data: Query<(Entity, &Particle)>,
+    mut pending: ResMut<PendingParticles>,
+    mut pool: ResMut<EntityPool<ParticleInternal>>,
 ) {
     for (eid, particle) in &data {
-        ParticleInternalBundle::spawn(particle.pos, particle, proot.eid(), &mut commands);
+        ParticleInternalBundle::spawn(
+            particle.pos,
+            particle,
+            proot.eid(),
+            &mut pool,
+            &mut commands,
+        );
         commands.entity(eid).despawn_recursive();
     }
+    for particle in pending.particles.drain(..) {
+        ParticleInternalBundle::spawn(
+            particle.pos,
+            &particle,
+            proot.eid(),
+            &mut pool,
+            &mut commands,
+        );
+    }
 }
 
 fn update_particles_internal(
-    mut particles: Query<(Entity, &mut ParticleInternal, &mut Sprite, &mut Transform)>,
+    mut particles: Query<
+        (
+            Entity,
+            &mut ParticleInternal,
+            &mut Sprite,
+            &mut Transform,
+            &mut Visibility,
+        ),
+        Without<Parked>,
+    >,
     mut commands: Commands,
     bullet_time: Res<BulletTime>,
     simple_spawners: Query<(&SimpleParticleSpawner, &GlobalTransform)>,
-    proot: Res<ParticlesRoot>,
+    mut pending: ResMut<PendingParticles>,
 ) {
-    for (eid, mut internal, mut sprite, mut tran) in &mut particles {
+    for (eid, mut internal, mut sprite, mut tran, mut visibility) in &mut particles {
         if internal.lifespan.finished() {
-            commands.entity(eid).despawn_recursive();
+            *visibility = Visibility::Hidden;
+            commands.entity(eid).insert((Parked, PhysicsSuppressed));
             continue;
         }
         internal.lifespan.tick(bullet_time.delta());
@@ -252,7 +299,7 @@ fn update_particles_internal(
         tran.translation.z -= bullet_time.delta_seconds();
     }
     for (simple_spawner, gtran) in &simple_spawners {
-        simple_spawner.do_spawn(gtran.translation().truncate(), &mut commands, &proot);
+        simple_spawner.do_spawn(gtran.translation().truncate(), &mut pending);
     }
 }
 
@@ -274,5 +321,8 @@ impl Plugin for ParticlesPlugin {
 
         app.register_type::<ParticleInternal>();
         app.register_type::<DynoAwareParticleSpawner>();
+
+        app.insert_resource(PendingParticles::default());
+        register_pool::<ParticleInternal>(app, 1024, PoolOverflow::RecycleOldest);
     }
 }
@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 use crate::prelude::*;
 
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
 pub enum Shape {
     Circle {
         center: Vec2,
@@ -51,20 +53,16 @@ impl Shape {
     /// out of the other. Returns None if they do not overlap. Otherwise, returns two things:
     /// 1. A diff which represents how much to move my placement by to get out of the shape
     /// 2. The exact collision point
-    fn bounce_off(
-        &self,
-        placement: (Vec2, f32),
-        rhs: ((&Self, &ShapeCache), Vec2, f32),
-    ) -> Option<(Vec2, Vec2)> {
+    fn bounce_off(&self, placement: (Vec2, f32), rhs: (&Self, Vec2, f32)) -> Option<(Vec2, Vec2)> {
         let (my_pos, _my_rot) = placement;
-        let (rhs_bounds, rhs_pos, rhs_rot) = rhs;
+        let (rhs_shape, rhs_pos, rhs_rot) = rhs;
         match self {
             Self::Circle {
                 center,
                 radius: my_radius,
             } => {
                 let my_pos = my_pos + *center;
-                let (signed_dist, cp) = rhs_bounds.0.closest_point((rhs_pos, rhs_rot), my_pos);
+                let (signed_dist, cp) = rhs_shape.closest_point((rhs_pos, rhs_rot), my_pos);
                 // NOTE: This abs is maybe not correct? Maybe it is?
                 // Basically it means we'll only bounce off if we're near the edge.
                 // If we're way inside another bounds, we're fucked, and we'll stay there forever.
@@ -84,16 +82,16 @@ impl Shape {
     }
 
     /// Given my placement and another shape/placement combo, figure out if these things overlap.
-    /// Returns None if they do not overlap. Otherwise, returns two things:
-    /// 1. A diff which represents how much to move my placement by to get out of the shape
-    /// 2. The exact collision point
+    /// NOTE: Triangulations are expected to already be in GLOBAL space (rotated and shifted),
+    /// and will be empty for circles.
     fn overlaps_with(
         &self,
-        cache: &ShapeCache,
+        my_triangles: &[Triangle],
         placement: (Vec2, f32),
-        rhs: ((&Self, &ShapeCache), Vec2, f32),
+        rhs: (&Self, &[Triangle], Vec2),
     ) -> bool {
-        match (self, rhs.0 .0) {
+        let (rhs_shape, rhs_triangles, rhs_pos) = rhs;
+        match (self, rhs_shape) {
             (
                 Self::Circle {
                     center: my_center,
@@ -104,61 +102,24 @@ impl Shape {
                     radius: other_radius,
                 },
             ) => {
-                return (*my_center + placement.0).distance(*other_center + rhs.1)
+                (*my_center + placement.0).distance(*other_center + rhs_pos)
                     < my_radius + other_radius
             }
-            (Self::Circle { center, radius }, Self::Polygon { .. }) => {
-                let ShapeCache::Polygon { triangulation } = rhs.0 .1 else {
-                    panic!("Shape cache doesn't match shape 0 in overlaps_with");
-                };
-                for triangle in triangulation {
-                    let triangle = triangle.clone().my_rotated(rhs.2).shifted(rhs.1);
-                    if triangle.signed_distance_to_point(*center + placement.0) < *radius {
-                        return true;
-                    }
-                }
-                return false;
-            }
-            (Self::Polygon { .. }, Self::Circle { center, radius }) => {
-                let ShapeCache::Polygon { triangulation } = cache else {
-                    panic!("Shape cache doesn't match shape 1 in overlaps_with");
-                };
-                for triangle in triangulation {
-                    let triangle = triangle
-                        .clone()
-                        .my_rotated(placement.1)
-                        .shifted(placement.0);
-                    if triangle.signed_distance_to_point(*center + rhs.1) < *radius {
-                        return true;
-                    }
-                }
-                return false;
-            }
+            (Self::Circle { center, radius }, Self::Polygon { .. }) => rhs_triangles
+                .iter()
+                .any(|triangle| triangle.signed_distance_to_point(*center + placement.0) < *radius),
+            (Self::Polygon { .. }, Self::Circle { center, radius }) => my_triangles
+                .iter()
+                .any(|triangle| triangle.signed_distance_to_point(*center + rhs_pos) < *radius),
             (Self::Polygon { .. }, Self::Polygon { .. }) => {
-                let ShapeCache::Polygon { triangulation: t1 } = cache else {
-                    panic!("Shape cache doesn't match shape 2 in overlaps_with");
-                };
-                let ShapeCache::Polygon { triangulation: t2 } = rhs.0 .1 else {
-                    panic!("Shape cache doesn't match shape 3 in overlaps_with");
-                };
-                let t1 = t1
-                    .iter()
-                    .map(|t| t.clone().my_rotated(placement.1))
-                    .map(|t| t.shifted(placement.0))
-                    .collect::<Vec<_>>();
-                let t2 = t2
-                    .iter()
-                    .map(|t| t.clone().my_rotated(rhs.2))
-                    .map(|t| t.shifted(rhs.1))
-                    .collect::<Vec<_>>();
-                for ta in t1.iter() {
-                    for tb in t2.iter() {
+                for ta in my_triangles {
+                    for tb in rhs_triangles {
                         if are_triangles_colliding(ta, tb) {
                             return true;
                         }
                     }
                 }
-                return false;
+                false
             }
         }
     }
@@ -190,33 +151,151 @@ impl Shape {
     }
 }
 
+/// Cheap volumes that fully contain a shape. Used to early-reject the (expensive) narrowphase checks
+/// when two shapes are nowhere near each other.
+#[derive(Debug, Clone, Reflect)]
+pub struct BoundingVolume {
+    pub center: Vec2,
+    pub radius: f32,
+    pub aabb: Rect,
+}
+impl BoundingVolume {
+    fn from_points(points: &[Vec2]) -> Self {
+        let aabb = points
+            .iter()
+            .fold(Rect::from_center_size(points[0], Vec2::ZERO), |rect, p| {
+                rect.union_point(*p)
+            });
+        let center = aabb.center();
+        let radius = points
+            .iter()
+            .map(|p| p.distance(center))
+            .fold(0.0, f32::max);
+        Self {
+            center,
+            radius,
+            aabb,
+        }
+    }
+
+    fn from_shape(shape: &Shape) -> Self {
+        match shape {
+            Shape::Circle { center, radius } => Self {
+                center: *center,
+                radius: *radius,
+                aabb: Rect::from_center_half_size(*center, Vec2::ONE * *radius),
+            },
+            Shape::Polygon { points } => Self::from_points(points),
+        }
+    }
+
+    pub fn my_rotated(self, angle: f32) -> Self {
+        let corners = [
+            self.aabb.min,
+            Vec2::new(self.aabb.min.x, self.aabb.max.y),
+            self.aabb.max,
+            Vec2::new(self.aabb.max.x, self.aabb.min.y),
+        ]
+        .map(|p| p.my_rotate(angle));
+        let aabb = corners
+            .iter()
+            .fold(Rect::from_center_size(corners[0], Vec2::ZERO), |rect, p| {
+                rect.union_point(*p)
+            });
+        Self {
+            center: self.center.my_rotate(angle),
+            radius: self.radius,
+            aabb,
+        }
+    }
+
+    pub fn shifted(self, vec: Vec2) -> Self {
+        Self {
+            center: self.center + vec,
+            radius: self.radius,
+            aabb: Rect::from_corners(self.aabb.min + vec, self.aabb.max + vec),
+        }
+    }
+
+    /// Returns false if these volumes definitely don't overlap. True means they _might_.
+    pub fn might_overlap(&self, rhs: &Self) -> bool {
+        if self.center.distance_squared(rhs.center) > (self.radius + rhs.radius).powi(2) {
+            return false;
+        }
+        !self.aabb.intersect(rhs.aabb).is_empty()
+    }
+}
+
 /// Data about a shape that helps with collision detection
 /// Calculated once when the shape is created.
 #[derive(Debug, Clone, Reflect)]
 enum ShapeCache {
-    Circle,
-    Polygon { triangulation: Vec<Triangle> },
+    Circle {
+        volume: BoundingVolume,
+    },
+    Polygon {
+        volume: BoundingVolume,
+        triangulation: Vec<Triangle>,
+    },
 }
 impl ShapeCache {
     fn from_shape(shape: &Shape) -> Self {
+        let volume = BoundingVolume::from_shape(shape);
         match shape {
-            Shape::Circle { .. } => Self::Circle,
+            Shape::Circle { .. } => Self::Circle { volume },
             Shape::Polygon { points } => Self::Polygon {
+                volume,
                 triangulation: triangulate(points),
             },
         }
     }
+
+    /// NOTE: Circles (like in the narrowphase) are shifted but not rotated
+    fn placed_volume(&self, placement: (Vec2, f32)) -> BoundingVolume {
+        match self {
+            Self::Circle { volume } => volume.clone().shifted(placement.0),
+            Self::Polygon { volume, .. } => {
+                volume.clone().my_rotated(placement.1).shifted(placement.0)
+            }
+        }
+    }
+
+    fn placed_triangulation(&self, placement: (Vec2, f32)) -> Vec<Triangle> {
+        match self {
+            Self::Circle { .. } => vec![],
+            Self::Polygon { triangulation, .. } => triangulation
+                .iter()
+                .map(|t| t.clone().my_rotated(placement.1).shifted(placement.0))
+                .collect(),
+        }
+    }
+}
+
+/// A world-space copy of the shape caches, at the placement it was last refreshed with.
+/// Lets static things (most notably big polygon planets) skip re-rotating all their triangles
+/// every single time something checks against them.
+#[derive(Debug, Clone, Reflect)]
+struct PlacedCache {
+    placement: (Vec2, f32),
+    volumes: Vec<BoundingVolume>,
+    triangulations: Vec<Vec<Triangle>>,
 }
 
 #[derive(Debug, Clone, Reflect)]
 pub struct Bounds {
     shapes_n_caches: Vec<(Shape, ShapeCache)>,
+    placed: Option<PlacedCache>,
+    /// If these bounds are duplicated around the room, how many of the shapes are the canonical ones
+    /// (the rest are copies offset by the mirage offsets)
+    wrapped_canonical: Option<usize>,
 }
 impl Bounds {
     pub fn from_shape(shape: Shape) -> Self {
         let cache = ShapeCache::from_shape(&shape);
         Self {
             shapes_n_caches: vec![(shape, cache)],
+            placed: None,
+            wrapped_canonical: None,
         }
     }
 
@@ -228,13 +307,88 @@ impl Bounds {
                 (s, cache)
             })
             .collect();
-        Self { shapes_n_caches }
+        Self {
+            shapes_n_caches,
+            placed: None,
+            wrapped_canonical: None,
+        }
+    }
+
+    /// Makes bounds that also exist one room away in every direction, so that things
+    /// can interact with them across the room wrap
+    pub fn from_wrapped_shapes(shapes: Vec<Shape>, room_state: &RoomState) -> Self {
+        let num_canonical = shapes.len();
+        let mut all_shapes = shapes.clone();
+        for offset in room_state.mirage_offsets() {
+            for shape in &shapes {
+                all_shapes.push(shape.clone().with_offset(offset));
+            }
+        }
+        let mut result = Self::from_shapes(all_shapes);
+        result.wrapped_canonical = Some(num_canonical);
+        result
+    }
+
+    /// Rebuilds the duplicated shapes for a (potentially) new room size. Does nothing to unwrapped bounds.
+    pub fn rewrap(&mut self, room_state: &RoomState) {
+        let Some(num_canonical) = self.wrapped_canonical else {
+            return;
+        };
+        let canonical = self
+            .shapes_n_caches
+            .iter()
+            .take(num_canonical)
+            .map(|(shape, _)| shape.clone())
+            .collect();
+        *self = Self::from_wrapped_shapes(canonical, room_state);
     }
 
     fn get_shapes_n_caches(&self) -> &[(Shape, ShapeCache)] {
         &self.shapes_n_caches
     }
 
+    /// Recomputes the world-space cache for this placement. Does nothing if it's already up to date.
+    pub fn refresh_placement(&mut self, placement: (Vec2, f32)) {
+        if self
+            .placed
+            .as_ref()
+            .is_some_and(|placed| placed.placement == placement)
+        {
+            return;
+        }
+        self.placed = Some(PlacedCache {
+            placement,
+            volumes: self
+                .shapes_n_caches
+                .iter()
+                .map(|(_, cache)| cache.placed_volume(placement))
+                .collect(),
+            triangulations: self
+                .shapes_n_caches
+                .iter()
+                .map(|(_, cache)| cache.placed_triangulation(placement))
+                .collect(),
+        });
+    }
+
+    /// Gets the cached world-space volume for shape ix, only computing it if the cache is stale
+    fn placed_volume(&self, ix: usize, placement: (Vec2, f32)) -> BoundingVolume {
+        match &self.placed {
+            Some(placed) if placed.placement == placement => placed.volumes[ix].clone(),
+            _ => self.shapes_n_caches[ix].1.placed_volume(placement),
+        }
+    }
+
+    /// Gets the cached world-space triangulation for shape ix, only computing it if the cache is stale
+    fn placed_triangulation(&self, ix: usize, placement: (Vec2, f32)) -> Cow<[Triangle]> {
+        match &self.placed {
+            Some(placed) if placed.placement == placement => {
+                Cow::Borrowed(&placed.triangulations[ix])
+            }
+            _ => Cow::Owned(self.shapes_n_caches[ix].1.placed_triangulation(placement)),
+        }
+    }
+
     pub fn draw(&self, pos: Vec2, rot: f32, gz: &mut Gizmos, color: Color) {
         for (shape, _) in self.get_shapes_n_caches() {
             // First draw the shape
@@ -260,12 +414,15 @@ impl Bounds {
         other_thing: (&Self, Vec2, f32),
     ) -> Option<(Vec2, Vec2)> {
         let (other_bounds, other_tran, other_angle) = other_thing;
-        for (my_shape, _my_cache) in &self.shapes_n_caches {
-            for (other_shape, other_cache) in other_bounds.get_shapes_n_caches() {
-                let bounce = my_shape.bounce_off(
-                    my_tran_n_angle,
-                    ((other_shape, other_cache), other_tran, other_angle),
-                );
+        for (ix, (my_shape, _)) in self.shapes_n_caches.iter().enumerate() {
+            let my_volume = self.placed_volume(ix, my_tran_n_angle);
+            for (jx, (other_shape, _)) in other_bounds.get_shapes_n_caches().iter().enumerate() {
+                let other_volume = other_bounds.placed_volume(jx, (other_tran, other_angle));
+                if !my_volume.might_overlap(&other_volume) {
+                    continue;
+                }
+                let bounce =
+                    my_shape.bounce_off(my_tran_n_angle, (other_shape, other_tran, other_angle));
                 if bounce.is_some() {
                     return bounce;
                 }
@@ -280,12 +437,20 @@ impl Bounds {
         other_thing: (&Self, Vec2, f32),
     ) -> bool {
         let (other_bounds, other_tran, other_angle) = other_thing;
-        for (my_shape, my_cache) in &self.shapes_n_caches {
-            for (other_shape, other_cache) in other_bounds.get_shapes_n_caches() {
+        for (ix, (my_shape, _)) in self.shapes_n_caches.iter().enumerate() {
+            let my_volume = self.placed_volume(ix, my_tran_n_angle);
+            for (jx, (other_shape, _)) in other_bounds.get_shapes_n_caches().iter().enumerate() {
+                let other_volume = other_bounds.placed_volume(jx, (other_tran, other_angle));
+                if !my_volume.might_overlap(&other_volume) {
+                    continue;
+                }
+                let my_triangles = self.placed_triangulation(ix, my_tran_n_angle);
+                let other_triangles =
+                    other_bounds.placed_triangulation(jx, (other_tran, other_angle));
                 if my_shape.overlaps_with(
-                    my_cache,
+                    &my_triangles,
                     my_tran_n_angle,
-                    ((other_shape, other_cache), other_tran, other_angle),
+                    (other_shape, &other_triangles, other_tran),
                 ) {
                     return true;
                 }
@@ -3,7 +3,12 @@ use crate::prelude::*;
 #[derive(Resource, Debug, Clone, Reflect)]
 pub struct BulletTime {
     time_factor: f32,
+    /// Slowdown from aiming (see `ship/focus.rs`). It's kept separate so that whatever else sets the time
+    /// factor (convos, tutorials, dying) doesn't fight with it. Whichever of the two is slower wins.
+    aim_factor: f32,
     main_duration: Duration,
+    /// When paused, no in-game time passes at all (regardless of the time factor)
+    paused: bool,
 }
 impl BulletTime {
     const NORMAL: f32 = 1.0;
@@ -12,7 +17,9 @@ impl BulletTime {
     pub fn new() -> Self {
         Self {
             time_factor: 1.0,
+            aim_factor: 1.0,
             main_duration: Duration::default(),
+            paused: false,
         }
     }
 
@@ -32,13 +39,39 @@ impl BulletTime {
         self.set_time_factor(Self::SLOW);
     }
 
+    impl_get!(time_factor, f32);
+
     pub fn set_time_factor(&mut self, factor: f32) {
         self.time_factor = factor;
     }
+
+    impl_get!(aim_factor, f32);
+
+    pub fn set_aim_factor(&mut self, factor: f32) {
+        self.aim_factor = factor;
+    }
+
+    /// Stops in-game time. The time factor is remembered for when it unpauses.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
 }
 
 fn update_bullet_time(mut bullet_time: ResMut<BulletTime>, time: Res<Time>) {
-    bullet_time.main_duration = time.delta().mul_f32(bullet_time.time_factor);
+    bullet_time.main_duration = if bullet_time.paused {
+        Duration::ZERO
+    } else {
+        time.delta()
+            .mul_f32(bullet_time.time_factor.min(bullet_time.aim_factor))
+    };
 }
 
 pub(super) struct BulletTimePlugin;
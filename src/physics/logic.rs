@@ -1,10 +1,43 @@
+use bevy::ecs::system::SystemParam;
+
 use crate::prelude::*;
 
 use super::{CollisionsSet, PhysicsInitialized};
 
 /// When moving `DynoTran`s that have a vel with mag greater than this number, the movement will
 /// occur in steps of this length to resolve collisions for fast-moving objects.
-const MAX_TRAN_STEP_LENGTH: f32 = 2.0;
+pub(super) const MAX_TRAN_STEP_LENGTH: f32 = 2.0;
+
+/// Hands out (pooled) entities for collision records. There can be a lot of these every frame.
+#[derive(SystemParam)]
+struct CollisionRecordSpawner<'w> {
+    root: Res<'w, CollisionRoot>,
+    static_pool: ResMut<'w, EntityPool<StaticCollisionRecord>>,
+    trigger_pool: ResMut<'w, EntityPool<TriggerCollisionRecord>>,
+}
+impl<'w> CollisionRecordSpawner<'w> {
+    fn spawn_static(&mut self, commands: &mut Commands, record: StaticCollisionRecord) -> Entity {
+        let eid = self
+            .static_pool
+            .acquire(commands, Some(self.root.eid()))
+            .expect("Static collision pool should always grow");
+        commands
+            .entity(eid)
+            .insert(StaticCollisionBundle::new(record));
+        eid
+    }
+
+    fn spawn_trigger(&mut self, commands: &mut Commands, record: TriggerCollisionRecord) -> Entity {
+        let eid = self
+            .trigger_pool
+            .acquire(commands, Some(self.root.eid()))
+            .expect("Trigger collision pool should always grow");
+        commands
+            .entity(eid)
+            .insert(TriggerCollisionBundle::new(record));
+        eid
+    }
+}
 
 /// Resets all records (collisions + triggers). Happens during PreUpdate
 fn reset_collision_records(
@@ -12,7 +45,13 @@ fn reset_collision_records(
     mut statics_receiver_q: Query<&mut StaticRx>,
     mut triggers_provider_q: Query<&mut TriggerTx>,
     mut triggers_receiver_q: Query<&mut TriggerRx>,
-    collision_root: Res<CollisionRoot>,
+    old_records: Query<
+        Entity,
+        (
+            Or<(With<StaticCollisionRecord>, With<TriggerCollisionRecord>)>,
+            Without<Parked>,
+        ),
+    >,
     mut commands: Commands,
 ) {
     for mut provider in statics_provider_q.iter_mut() {
@@ -27,7 +66,9 @@ fn reset_collision_records(
     for mut receiver in triggers_receiver_q.iter_mut() {
         receiver.collisions = VecDeque::new();
     }
-    commands.entity(collision_root.eid()).despawn_descendants();
+    for eid in &old_records {
+        commands.entity(eid).insert(Parked);
+    }
 }
 
 /// Enforces current limitations in the physics system by panicking if I ever fuck up.
@@ -80,15 +121,42 @@ fn initialize_physics(
                 With<TriggerRx>,
             )>,
             Without<PhysicsInitialized>,
+            Without<PhysicsSuppressed>,
+            Without<Parked>,
+        ),
+    >,
+    suppressed_eids: Query<
+        Entity,
+        (
+            Or<(With<PhysicsSuppressed>, With<Parked>)>,
+            With<PhysicsInitialized>,
         ),
     >,
 ) {
     for eid in &relevant_eids {
         commands.entity(eid).insert(PhysicsInitialized);
     }
+    for eid in &suppressed_eids {
+        commands.entity(eid).remove::<PhysicsInitialized>();
+    }
 }
 
-/// Moves all dynos (both rot and tran) that are not statics, do not collide with statics, and have no triggers
+/// Refreshes the world-space caches on provider bounds whose transform has changed.
+/// Receivers move mid-step during collision resolution, so caching them isn't worth it.
+fn refresh_placed_bounds(
+    mut static_txs: Query<(&GlobalTransform, &mut StaticTx), Changed<GlobalTransform>>,
+    mut trigger_txs: Query<(&GlobalTransform, &mut TriggerTx), Changed<GlobalTransform>>,
+) {
+    for (gtran, mut tx) in &mut static_txs {
+        tx.bounds.refresh_placement(gtran.pos_n_angle());
+    }
+    for (gtran, mut tx) in &mut trigger_txs {
+        tx.bounds.refresh_placement(gtran.pos_n_angle());
+    }
+}
+
+/// Moves all dynos (both rot and tran) that are not statics, do not collide with statics, and do not receive triggers.
+/// Trigger providers (i.e. explosions) just move, their bounds follow along in `refresh_placed_bounds`.
 fn move_uninteresting_dynos(
     bullet_time: Res<BulletTime>,
     mut rot_only_dynos: Query<
@@ -97,7 +165,6 @@ fn move_uninteresting_dynos(
             Without<DynoTran>,
             Without<StaticTx>,
             Without<StaticRx>,
-            Without<TriggerTx>,
             Without<TriggerRx>,
             With<PhysicsInitialized>,
         ),
@@ -107,7 +174,6 @@ fn move_uninteresting_dynos(
         (
             Without<StaticTx>,
             Without<StaticRx>,
-            Without<TriggerTx>,
             Without<TriggerRx>,
             With<PhysicsInitialized>,
         ),
@@ -118,7 +184,6 @@ fn move_uninteresting_dynos(
             Without<DynoRot>,
             Without<StaticTx>,
             Without<StaticRx>,
-            Without<TriggerTx>,
             Without<TriggerRx>,
             With<PhysicsInitialized>,
         ),
@@ -182,6 +247,44 @@ fn move_static_provider_dynos(
     }
 }
 
+/// What happens to a receiver moving at `vel` once it's been pushed out of a provider by `mvmt`.
+/// Returns the new velocity, and whether the receiver should now be stuck to the provider.
+/// NOTE: Trajectory prediction (see `prediction.rs`) uses this too, so keep it free of side effects.
+pub(super) fn static_response(
+    tx_kind: StaticTxKind,
+    rx: &StaticRx,
+    vel: Vec2,
+    mvmt: Vec2,
+) -> (Vec2, bool) {
+    let bounce_with_friction = |vel: Vec2, springiness: f32, friction: f32| -> Vec2 {
+        // TODO: All these normalize_or_zero's are probably a bit slow, fix later
+        let old_perp = vel.dot(mvmt.normalize_or_zero()) * mvmt.normalize_or_zero();
+        let old_par = vel - old_perp;
+        let mut new_perp = old_perp * springiness;
+        if new_perp.dot(mvmt) < 0.0 {
+            new_perp *= -1.0;
+        }
+        let friction_mult =
+            1.0 + vel.normalize_or_zero().dot(mvmt.normalize_or_zero()).abs() * 10.0;
+        let new_par = old_par * (1.0 - (friction * friction_mult).min(1.0));
+        new_perp + new_par
+    };
+    let old_perp = vel.dot(mvmt.normalize_or_zero()) * mvmt.normalize_or_zero();
+    match (tx_kind, rx.kind) {
+        (_, StaticRxKind::Stop) => (Vec2::ZERO, false),
+        (_, StaticRxKind::GoAround { mult }) => {
+            // Try to move perpendicularly around this thing
+            // TODO: Come up with a better system so we don't have to do this
+            (vel + Vec2::new(mvmt.y, -mvmt.x) * mult as f32, false)
+        }
+        (StaticTxKind::Normal, StaticRxKind::Normal) if old_perp.length() >= rx.grip => {
+            (bounce_with_friction(vel, 0.2, 0.03), false)
+        }
+        // Sticky things always stick, normal things only if the receiver has enough grip
+        (_, StaticRxKind::Normal) => (Vec2::ZERO, true),
+    }
+}
+
 /// A helper function to resolve static collisions for a single entity. This will do the work of pushing the
 /// entity given by eid outside of other entities it's colliding with
 fn resolve_static_collisions(
@@ -190,9 +293,12 @@ fn resolve_static_collisions(
     dyno_tran: &mut DynoTran,
     tran: &mut Transform,
     gtran_offset: Vec2,
-    providers: &mut Query<(Entity, &mut StaticTx, &GlobalTransform)>,
+    providers: &mut Query<
+        (Entity, &mut StaticTx, &GlobalTransform),
+        (Without<PhysicsSuppressed>, Without<Parked>),
+    >,
     commands: &mut Commands,
-    collision_root: &CollisionRoot,
+    record_spawner: &mut CollisionRecordSpawner,
 ) {
     for (tx_eid, mut tx, tx_gtran) in providers {
         // Correct the global/local translation and see if there is a collision
@@ -219,50 +325,22 @@ fn resolve_static_collisions(
             rx_eid,
             rx_kind: rx.kind,
         };
-        let collision_eid = commands
-            .spawn(StaticCollisionBundle::new(collision_record))
-            .set_parent(collision_root.eid())
-            .id();
+        let collision_eid = record_spawner.spawn_static(commands, collision_record);
         rx.collisions.push_back(collision_eid);
         tx.collisions.push_back(collision_eid);
 
         // Then actually move the objects out of each other and handle physics updates
         tran.translation += mvmt.extend(0.0);
-        let bounce_with_friction = |vel: Vec2, springiness: f32, friction: f32| -> Vec2 {
-            // TODO: All these normalize_or_zero's are probably a bit slow, fix later
-            let old_perp = vel.dot(mvmt.normalize_or_zero()) * mvmt.normalize_or_zero();
-            let old_par = vel - old_perp;
-            let mut new_perp = old_perp * springiness;
-            if new_perp.dot(mvmt) < 0.0 {
-                new_perp *= -1.0;
-            }
-            let friction_mult =
-                1.0 + vel.normalize_or_zero().dot(mvmt.normalize_or_zero()).abs() * 10.0;
-            let new_par = old_par * (1.0 - (friction * friction_mult).min(1.0));
-            new_perp + new_par
-        };
-        match (tx.kind, rx.kind) {
-            (_, StaticRxKind::Stop) => {
-                dyno_tran.vel = Vec2::ZERO;
-            }
-            (_, StaticRxKind::GoAround { mult }) => {
-                // Try to move perpendicularly around this thing
-                // TODO: Come up with a better system so we don't have to do this
-                dyno_tran.vel += Vec2::new(mvmt.y, -mvmt.x) * mult as f32;
-            }
-            (StaticTxKind::Normal, StaticRxKind::Normal) => {
-                dyno_tran.vel = bounce_with_friction(dyno_tran.vel, 0.2, 0.03);
-            }
-            (StaticTxKind::Sticky, StaticRxKind::Normal) => {
-                dyno_tran.vel = Vec2::ZERO;
-                let stuck_marker = Stuck {
-                    parent: tx_eid,
-                    my_initial_angle: my_tran_n_angle.1,
-                    parent_initial_angle: rhs_tran_n_angle.1,
-                    initial_offset: tran.translation.truncate() + gtran_offset - rhs_tran_n_angle.0,
-                };
-                commands.entity(rx_eid).insert(stuck_marker);
-            }
+        let (new_vel, sticks) = static_response(tx.kind, rx, dyno_tran.vel, mvmt);
+        dyno_tran.vel = new_vel;
+        if sticks {
+            let stuck_marker = Stuck {
+                parent: tx_eid,
+                my_initial_angle: my_tran_n_angle.1,
+                parent_initial_angle: rhs_tran_n_angle.1,
+                initial_offset: tran.translation.truncate() + gtran_offset - rhs_tran_n_angle.0,
+            };
+            commands.entity(rx_eid).insert(stuck_marker);
         }
     }
 }
@@ -278,9 +356,12 @@ fn resolve_trigger_collisions(
     rx: &mut TriggerRx,
     gtran: &Transform,
     shared_data: &Query<(Entity, &GlobalTransform)>,
-    trigger_txs: &mut Query<(Entity, &mut TriggerTx)>,
+    trigger_txs: &mut Query<
+        (Entity, &mut TriggerTx),
+        (Without<PhysicsSuppressed>, Without<Parked>),
+    >,
     commands: &mut Commands,
-    collision_root: &CollisionRoot,
+    record_spawner: &mut CollisionRecordSpawner,
     dup_set: &mut HashSet<(Entity, Entity)>,
 ) {
     for (other_eid, mut other_tx) in trigger_txs {
@@ -307,10 +388,7 @@ fn resolve_trigger_collisions(
                 tx_eid: other_eid,
                 tx_kind: other_tx.kind.clone(),
             };
-            let my_collision_eid = commands
-                .spawn(TriggerCollisionBundle::new(my_collision_record))
-                .set_parent(collision_root.eid())
-                .id();
+            let my_collision_eid = record_spawner.spawn_trigger(commands, my_collision_record);
             rx.collisions.push_back(my_collision_eid);
             let other_collision_record = TriggerCollisionRecord {
                 my_role: TriggerCollisionRole::Tx,
@@ -319,10 +397,8 @@ fn resolve_trigger_collisions(
                 tx_eid: other_eid,
                 tx_kind: other_tx.kind.clone(),
             };
-            let other_collision_eid = commands
-                .spawn(TriggerCollisionBundle::new(other_collision_record))
-                .set_parent(collision_root.eid())
-                .id();
+            let other_collision_eid =
+                record_spawner.spawn_trigger(commands, other_collision_record);
             other_tx.collisions.push_back(other_collision_eid);
             dup_set.insert((eid, other_eid));
         }
@@ -353,12 +429,15 @@ fn move_unstuck_static_or_trigger_receivers(
         Or<(With<DynoTran>, With<DynoRot>)>,
     >,
     mut static_data: Query<(Entity, &mut StaticRx), Without<Stuck>>,
-    mut trigger_txs: Query<(Entity, &mut TriggerTx)>,
-    mut trigger_rxs: Query<(Entity, &mut TriggerRx)>,
-    mut static_txs: Query<(Entity, &mut StaticTx, &GlobalTransform)>,
+    mut trigger_txs: Query<(Entity, &mut TriggerTx), (Without<PhysicsSuppressed>, Without<Parked>)>,
+    mut trigger_rxs: Query<(Entity, &mut TriggerRx), Without<Parked>>,
+    mut static_txs: Query<
+        (Entity, &mut StaticTx, &GlobalTransform),
+        (Without<PhysicsSuppressed>, Without<Parked>),
+    >,
     mut commands: Commands,
-    collision_root: Res<CollisionRoot>,
-    proot: Res<ParticlesRoot>,
+    mut record_spawner: CollisionRecordSpawner,
+    mut pending_particles: ResMut<PendingParticles>,
 ) {
     for eid in &relevant_eids {
         // Shared data (immutable)
@@ -406,7 +485,7 @@ fn move_unstuck_static_or_trigger_receivers(
                         my_gtran_offset,
                         &mut static_txs,
                         &mut commands,
-                        &collision_root,
+                        &mut record_spawner,
                     );
                 }
                 // Basically because GlobalTransform doesn't update mid-system we need to do this shenanigans
@@ -420,7 +499,7 @@ fn move_unstuck_static_or_trigger_receivers(
                         &shared_data,
                         &mut trigger_txs,
                         &mut commands,
-                        &collision_root,
+                        &mut record_spawner,
                         &mut dup_set,
                     );
                 }
@@ -428,8 +507,7 @@ fn move_unstuck_static_or_trigger_receivers(
                 if let Some(particle_spawner) = particle_spawner {
                     particle_spawner.do_spawn(
                         mid_step_gtran.translation.truncate(),
-                        &mut commands,
-                        &proot,
+                        &mut pending_particles,
                     );
                 }
                 // Update the loop stuff
@@ -450,7 +528,7 @@ fn move_unstuck_static_or_trigger_receivers(
                     &shared_data,
                     &mut trigger_txs,
                     &mut commands,
-                    &collision_root,
+                    &mut record_spawner,
                     &mut dup_set,
                 );
             }
@@ -499,8 +577,7 @@ fn move_stuck_static_receiver_dynos(
         ),
     >,
     static_providers: Query<&GlobalTransform, With<StaticTx>>,
-    mut commands: Commands,
-    proot: Res<ParticlesRoot>,
+    mut pending_particles: ResMut<PendingParticles>,
 ) {
     // First move the things
     for (_eid, stuck, mut dyno_tran, mut tran, particle_spawner) in &mut stuck_dynos {
@@ -516,26 +593,33 @@ fn move_stuck_static_receiver_dynos(
         tran.translation.y = provider_tran.y + rotated_pos.y;
         // If we have a physics-based particle spawner, do something!
         if let Some(particle_spawner) = particle_spawner {
-            particle_spawner.do_spawn(tran.translation.truncate(), &mut commands, &proot);
+            particle_spawner.do_spawn(tran.translation.truncate(), &mut pending_particles);
         }
     }
 }
 
+/// Where `pos` ends up after wrapping around the edges of the room
+pub(super) fn room_wrapped(pos: Vec2, room_state: &RoomState) -> Vec2 {
+    let half_room_size = room_state.room_size.as_vec2() / 2.0;
+    let wrapped_x =
+        (pos.x + half_room_size.x).rem_euclid(half_room_size.x * 2.0) - half_room_size.x;
+    let wrapped_y =
+        (pos.y + half_room_size.y).rem_euclid(half_room_size.y * 2.0) - half_room_size.y;
+    Vec2::new(wrapped_x, wrapped_y)
+}
+
 fn apply_room_wrap(
     mut ents: Query<(&mut Transform, &GlobalTransform), With<RoomWrap>>,
-    room_state: Res<State<RoomState>>,
+    meta_state: Res<State<MetaState>>,
 ) {
-    let room_state = room_state.get();
+    // Tutorials wrap around their stand-in room too, otherwise the ship can fly off forever
+    let Some(room_state) = meta_state.play_room_state() else {
+        return;
+    };
     for (mut tran, gtran) in &mut ents {
-        let half_room_size = room_state.room_size.as_vec2() / 2.0;
-        let wrapped_x = (gtran.translation().x + half_room_size.x)
-            .rem_euclid(half_room_size.x * 2.0)
-            - half_room_size.x;
-        let wrapped_y = (gtran.translation().y + half_room_size.y)
-            .rem_euclid(half_room_size.y * 2.0)
-            - half_room_size.y;
-        tran.translation.x += wrapped_x - gtran.translation().x;
-        tran.translation.y += wrapped_y - gtran.translation().y;
+        let wrapped = room_wrapped(gtran.translation().truncate(), &room_state);
+        tran.translation.x += wrapped.x - gtran.translation().x;
+        tran.translation.y += wrapped.y - gtran.translation().y;
     }
 }
 
@@ -558,6 +642,9 @@ fn shephard_bullet_update(
 }
 
 pub(super) fn register_logic(app: &mut App) {
+    // Collision records are pooled, and should never be dropped
+    register_pool::<StaticCollisionRecord>(app, 64, PoolOverflow::Grow);
+    register_pool::<TriggerCollisionRecord>(app, 64, PoolOverflow::Grow);
     // Reset collisions during preupdate
     app.add_systems(
         PreUpdate,
@@ -573,6 +660,15 @@ pub(super) fn register_logic(app: &mut App) {
             .run_if(in_state(PhysicsState::Active))
             .run_if(in_state(AppMode::Dev)),
     );
+    // Keep the world-space bounds caches fresh before anything collides
+    app.add_systems(
+        Update,
+        refresh_placed_bounds
+            .in_set(PhysicsSet)
+            .after(InputSet)
+            .before(CollisionsSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
     // Systems for detecting and resolving collisions
     app.add_systems(
         Update,
@@ -594,7 +690,7 @@ pub(super) fn register_logic(app: &mut App) {
         apply_room_wrap
             .in_set(PhysicsSet)
             .after(CollisionsSet)
-            .run_if(in_state(MetaStateKind::Room)),
+            .run_if(in_state(MetaStateKind::Room).or_else(in_state(MetaStateKind::Tutorial))),
     );
     // Stuff for the BulletUpdate schedule
     app.init_schedule(BulletUpdate);
@@ -8,6 +8,7 @@ pub mod dyno;
 pub mod follow;
 mod logic;
 pub mod patrol;
+pub mod prediction;
 pub mod statics;
 pub mod triggers;
 
@@ -18,6 +19,7 @@ pub use bullet_time::*;
 pub use dyno::*;
 pub use follow::*;
 pub use patrol::*;
+pub use prediction::*;
 pub use statics::*;
 pub use triggers::*;
 
@@ -35,6 +37,12 @@ struct CollisionsSet;
 #[derive(Component)]
 struct PhysicsInitialized;
 
+/// Marks an entity that physics should ignore for now. It won't move, collide, or trigger anything
+/// until this is removed (at which point it gets re-initialized like it was just spawned).
+/// NOTE: Usually you want `Birthing::with_suppress_physics` rather than inserting this yourself.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct PhysicsSuppressed;
+
 /// A schedule that will run every FRAMERATE of IN-GAME time
 /// So things like drag will be applied consistently in and out of bullet time
 #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
@@ -44,6 +52,7 @@ pub(super) struct PhysicsPlugin;
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<StaticTx>();
+        app.register_type::<PhysicsSuppressed>();
 
         app.add_plugins(bullet_time::BulletTimePlugin);
         collisions::register_collisions(app);
@@ -0,0 +1,75 @@
+//! Predicts where a static receiver will go if it starts moving with some velocity right now.
+//! It steps and collides exactly like `move_unstuck_static_or_trigger_receivers` does (same step length, same
+//! `static_response`, same room wrap), just without touching the world.
+//!
+//! Providers are taken as they are right now, they don't move or rotate during the prediction.
+//! NOTE: Nothing applies forces to dynos yet. If force fields ever show up, they need to be applied here too.
+
+use crate::prelude::*;
+
+use super::logic::{room_wrapped, static_response, MAX_TRAN_STEP_LENGTH};
+
+#[derive(Debug, Clone, Default)]
+pub struct PredictedPath {
+    /// Where the receiver is at the end of every frame (starting with where it is now)
+    pub points: Vec<Vec2>,
+    /// Where it first touches a static (if it does)
+    pub contact: Option<Vec2>,
+    /// Whether it ends up stuck to something
+    pub stuck: bool,
+}
+
+/// Simulates `rx` starting at `pos` with `vel` for `max_time` (in-game seconds) against `providers`
+/// (placed at their current position and angle). Wraps around the room if `wrap` is given.
+pub fn predict_static_path<'a>(
+    rx: &StaticRx,
+    pos: Vec2,
+    vel: Vec2,
+    providers: impl Iterator<Item = (&'a StaticTx, (Vec2, f32))> + Clone,
+    wrap: Option<&RoomState>,
+    max_time: f32,
+) -> PredictedPath {
+    let dt = 1.0 / FRAMERATE;
+    let angle = vel.to_angle();
+    let mut pos = pos;
+    let mut vel = vel;
+    let mut time = 0.0;
+    let mut path = PredictedPath {
+        points: vec![pos],
+        ..default()
+    };
+    while time < max_time && vel != Vec2::ZERO {
+        time += dt;
+        let mut amount_moved = 0.0;
+        let mut total_to_move = vel.length() * dt;
+        while amount_moved < total_to_move {
+            let dir = vel.normalize_or_zero();
+            let mag = (vel.length() * dt - amount_moved).min(MAX_TRAN_STEP_LENGTH);
+            pos += dir * mag;
+            for (tx, tx_placement) in providers.clone() {
+                let Some((mvmt, cp)) = rx
+                    .bounds
+                    .bounce_off((pos, angle), (&tx.bounds, tx_placement.0, tx_placement.1))
+                else {
+                    continue;
+                };
+                pos += mvmt;
+                path.contact.get_or_insert(cp);
+                let (new_vel, sticks) = static_response(tx.kind, rx, vel, mvmt);
+                vel = new_vel;
+                if sticks {
+                    path.stuck = true;
+                    path.points.push(pos);
+                    return path;
+                }
+            }
+            amount_moved += MAX_TRAN_STEP_LENGTH;
+            total_to_move = total_to_move.min(vel.length() * dt);
+        }
+        if let Some(room_state) = wrap {
+            pos = room_wrapped(pos, room_state);
+        }
+        path.points.push(pos);
+    }
+    path
+}
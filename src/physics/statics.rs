@@ -1,7 +1,7 @@
 use crate::prelude::*;
 
 /// Different ways of providing a static collision hitbox. Admits the design space (StaticTxKind x StaticRxKind)
-#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq, Serialize, Deserialize)]
 pub enum StaticTxKind {
     /// Objects will stick to the outside.
     Sticky,
@@ -32,6 +32,18 @@ impl StaticTx {
             collisions: default(),
         }
     }
+
+    pub fn from_kind_n_wrapped_shapes(
+        kind: StaticTxKind,
+        shapes: Vec<Shape>,
+        room_state: &RoomState,
+    ) -> Self {
+        Self {
+            kind,
+            bounds: Bounds::from_wrapped_shapes(shapes, room_state),
+            collisions: default(),
+        }
+    }
 }
 
 /// Different ways of interacting with statics on collision.
@@ -54,6 +66,8 @@ pub struct StaticRx {
     pub kind: StaticRxKind,
     pub bounds: Bounds,
     pub collisions: VecDeque<Entity>,
+    /// `Normal` receivers will also stick to normal statics if they hit them slower than this
+    pub grip: f32,
 }
 impl StaticRx {
     pub fn from_kind_n_shape(kind: StaticRxKind, shape: Shape) -> Self {
@@ -61,6 +75,20 @@ impl StaticRx {
             kind,
             bounds: Bounds::from_shape(shape),
             collisions: default(),
+            grip: 0.0,
+        }
+    }
+
+    pub fn from_kind_n_wrapped_shapes(
+        kind: StaticRxKind,
+        shapes: Vec<Shape>,
+        room_state: &RoomState,
+    ) -> Self {
+        Self {
+            kind,
+            bounds: Bounds::from_wrapped_shapes(shapes, room_state),
+            collisions: default(),
+            grip: 0.0,
         }
     }
 }
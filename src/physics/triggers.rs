@@ -5,6 +5,12 @@ use crate::prelude::*;
 pub enum TriggerKind {
     /// Basically marks the hitbox of the protagonist
     Ship,
+    /// Hurts the ship while it overlaps
+    Explosion,
+    /// Something the ship shot (see `Projectile`)
+    Bullet,
+    /// Marks the hurtbox of an enemy
+    Enemy,
 }
 
 /// Marks an object as being a trigger provider
@@ -30,6 +36,18 @@ impl TriggerTx {
             collisions: default(),
         }
     }
+
+    pub fn from_kind_n_wrapped_shapes(
+        kind: TriggerKind,
+        shapes: Vec<Shape>,
+        room_state: &RoomState,
+    ) -> Self {
+        Self {
+            kind,
+            bounds: Bounds::from_wrapped_shapes(shapes, room_state),
+            collisions: default(),
+        }
+    }
 }
 
 /// Marks an object as being a trigger receiver
@@ -0,0 +1,164 @@
+//! Spawning and despawning lots of short-lived things every frame (particles, collision records,
+//! explosions) is surprisingly expensive, especially on mobile.
+//! An `EntityPool` hands out entities, and then takes them back once they're `Parked` so
+//! they can be reused instead of despawned.
+//!
+//! Usage:
+//! - Call `register_pool::<K>` with a key type (usually the main component of the pooled thing)
+//! - `acquire` an entity from `EntityPool<K>` and insert whatever bundle you want onto it
+//! - When it's done, insert `Parked` (and hide it if it's visible, and suppress its physics if it has any).
+//!   Anything with `Pooled` that finishes `Dying` gets parked automatically instead of despawned.
+//!   Physics ignores anything `Parked` either way, so parked things never move or collide.
+
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolSet;
+
+/// What to do when something wants an entity from a pool that is at its cap
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq)]
+pub enum PoolOverflow {
+    /// Spawn a new entity anyway. The cap is basically just a soft limit.
+    Grow,
+    /// Steal the entity that has been out of the pool the longest
+    RecycleOldest,
+    /// Don't hand anything out
+    Drop,
+}
+
+/// Marks an entity as owned by some pool. The lifecycle will park these instead of despawning them.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Pooled;
+
+/// Marks a pooled entity that is not currently in use. Systems that operate on pooled things
+/// should ignore anything with this.
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Parked;
+
+/// Which pool a pooled entity belongs to
+#[derive(Component, Debug, Clone)]
+pub struct PoolMember<K: Send + Sync + 'static> {
+    _pd: PhantomData<K>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct EntityPool<K: Send + Sync + 'static> {
+    cap: usize,
+    overflow: PoolOverflow,
+    /// Entities that are out in the world doing stuff, oldest first
+    active: VecDeque<Entity>,
+    /// Entities that are ready to be reused
+    parked: Vec<Entity>,
+    _pd: PhantomData<K>,
+}
+impl<K: Send + Sync + 'static> EntityPool<K> {
+    pub fn new(cap: usize, overflow: PoolOverflow) -> Self {
+        Self {
+            cap,
+            overflow,
+            active: default(),
+            parked: default(),
+            _pd: default(),
+        }
+    }
+
+    impl_get_set_with!(cap, usize);
+    impl_get_set_with!(overflow, PoolOverflow);
+
+    pub fn num_active(&self) -> usize {
+        self.active.len()
+    }
+
+    pub fn num_parked(&self) -> usize {
+        self.parked.len()
+    }
+
+    fn spawn_fresh(&mut self, commands: &mut Commands, parent: Option<Entity>) -> Entity {
+        let mut ent_comm = commands.spawn((
+            Name::new("pooled"),
+            Pooled,
+            PoolMember::<K> { _pd: default() },
+        ));
+        if let Some(parent) = parent {
+            ent_comm.set_parent(parent);
+        }
+        let eid = ent_comm.id();
+        self.active.push_back(eid);
+        eid
+    }
+
+    /// Gets an entity to use. Reuses a parked entity if possible, otherwise spawns one
+    /// (subject to the cap and overflow policy). The parent is only set for fresh entities,
+    /// recycled ones keep the parent they had.
+    /// NOTE: The returned entity will have whatever components it had last time it was used.
+    /// You're expected to immediately insert a full bundle to overwrite them.
+    pub fn acquire(&mut self, commands: &mut Commands, parent: Option<Entity>) -> Option<Entity> {
+        while let Some(eid) = self.parked.pop() {
+            let Some(mut ent_comm) = commands.get_entity(eid) else {
+                // Got despawned out from under us, whatever
+                continue;
+            };
+            ent_comm.remove::<(Parked, PhysicsSuppressed)>();
+            self.active.push_back(eid);
+            return Some(eid);
+        }
+        if self.active.len() < self.cap {
+            return Some(self.spawn_fresh(commands, parent));
+        }
+        match self.overflow {
+            PoolOverflow::Grow => Some(self.spawn_fresh(commands, parent)),
+            PoolOverflow::RecycleOldest => {
+                while let Some(eid) = self.active.pop_front() {
+                    let Some(mut ent_comm) = commands.get_entity(eid) else {
+                        continue;
+                    };
+                    // Might have been parked this frame, but not reclaimed yet
+                    ent_comm.remove::<(Parked, PhysicsSuppressed)>();
+                    self.active.push_back(eid);
+                    return Some(eid);
+                }
+                Some(self.spawn_fresh(commands, parent))
+            }
+            PoolOverflow::Drop => None,
+        }
+    }
+
+    fn reclaim(&mut self, eid: Entity) {
+        // Things tend to be parked in roughly the order they were acquired, so this is usually quick
+        if let Some(ix) = self.active.iter().position(|other| *other == eid) {
+            self.active.remove(ix);
+            self.parked.push(eid);
+        }
+    }
+
+    fn forget(&mut self, eid: Entity) {
+        self.active.retain(|other| *other != eid);
+        self.parked.retain(|other| *other != eid);
+    }
+}
+
+/// Moves anything that was just parked back into the pool, and forgets anything that was despawned
+fn reclaim_parked<K: Send + Sync + 'static>(
+    mut pool: ResMut<EntityPool<K>>,
+    newly_parked: Query<Entity, (With<PoolMember<K>>, Added<Parked>)>,
+    mut despawned: RemovedComponents<PoolMember<K>>,
+) {
+    for eid in &newly_parked {
+        pool.reclaim(eid);
+    }
+    for eid in despawned.read() {
+        pool.forget(eid);
+    }
+}
+
+pub fn register_pool<K: Send + Sync + 'static>(app: &mut App, cap: usize, overflow: PoolOverflow) {
+    app.register_type::<Pooled>();
+    app.register_type::<Parked>();
+    app.insert_resource(EntityPool::<K>::new(cap, overflow));
+    app.add_systems(
+        PreUpdate,
+        reclaim_parked::<K>.in_set(PoolSet).after(PhysicsSet),
+    );
+}
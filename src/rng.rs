@@ -0,0 +1,81 @@
+//! All gameplay randomness comes from `GameRng`, so a seed fully determines a run (and bugs can be replayed).
+//!
+//! Randomness is split into streams by subsystem. Each stream has its own generator derived from the seed,
+//! so i.e. adding a particle effect doesn't change where enemies spawn.
+//! Things that need a generator of their own (i.e. generating a room) can `fork` one off a stream.
+//!
+//! The seed is picked at app start. Set `ZENITH_SEED` to use a specific one, otherwise it's random
+//! (and logged, so it can be reused).
+
+use crate::prelude::*;
+
+const SEED_ENV_VAR: &str = "ZENITH_SEED";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum RngStream {
+    /// Enemy decisions and spawning
+    Ai,
+    /// Room layout and anything else that affects gameplay
+    Environment,
+    Particles,
+    /// Purely visual stuff (stars, z-nudges)
+    Cosmetic,
+}
+impl RngStream {
+    /// Mixed into the seed so each stream gets a different sequence
+    fn salt(&self) -> u64 {
+        let ix = match self {
+            Self::Ai => 1,
+            Self::Environment => 2,
+            Self::Particles => 3,
+            Self::Cosmetic => 4,
+        };
+        ix * 0x9E37_79B9_7F4A_7C15
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct GameRng {
+    seed: u64,
+    streams: HashMap<RngStream, StdRng>,
+}
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: default(),
+        }
+    }
+
+    impl_get!(seed, u64);
+
+    /// Starts every stream over from a new seed
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+
+    pub fn stream(&mut self, stream: RngStream) -> &mut StdRng {
+        let seed = self.seed;
+        self.streams
+            .entry(stream)
+            .or_insert_with(|| StdRng::seed_from_u64(seed ^ stream.salt()))
+    }
+
+    /// A new generator seeded from the given stream
+    pub fn fork(&mut self, stream: RngStream) -> StdRng {
+        StdRng::seed_from_u64(self.stream(stream).gen())
+    }
+}
+
+/// Picks the seed at app start
+pub(super) struct RngPlugin;
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let from_env = std::env::var(SEED_ENV_VAR)
+            .ok()
+            .and_then(|seed| seed.parse::<u64>().ok());
+        let seed = from_env.unwrap_or_else(rand::random);
+        info!("Using rng seed {seed} (set {SEED_ENV_VAR} to reuse it)");
+        app.insert_resource(GameRng::new(seed));
+    }
+}
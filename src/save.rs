@@ -0,0 +1,628 @@
+//! Persists progress between sessions.
+//! There's one save file (`save.ron`) in the platform's data directory. It holds the current run
+//! (what room/encounter you're on, and stats like deaths per progression node) and the settings.
+//!
+//! Every save file has a version (files from before there was one count as version 0). When the format changes:
+//! - Bump `SAVE_VERSION`
+//! - Keep the old struct around (i.e. `SaveFileV1`) with an `upgrade` that turns it into the next version
+//! - Add an arm to `migrate` that parses it and upgrades it all the way to the current format
+//! That way old saves keep working instead of silently resetting everyone's progress. A file that still can't be
+//! read (broken, or from a newer build) is copied to `save.ron.bak` before anything overwrites it.
+
+use std::path::PathBuf;
+
+use crate::prelude::*;
+
+pub const SAVE_VERSION: u32 = 2;
+const SAVE_FILE_NAME: &str = "save.ron";
+const BACKUP_FILE_NAME: &str = "save.ron.bak";
+#[cfg_attr(target_os = "android", allow(dead_code))]
+const APP_DIR_NAME: &str = "zenith";
+
+/// Player-facing settings. These are saved whenever they change.
+#[derive(Resource, Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct GameSettings {
+    pub music_volume: f32,
+    pub effects_volume: f32,
+}
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.0,
+            effects_volume: 1.0,
+        }
+    }
+}
+impl GameSettings {
+    pub fn volume_for(&self, kind: AudioKind) -> f32 {
+        match kind {
+            AudioKind::Music => self.music_volume,
+            AudioKind::Effects => self.effects_volume,
+        }
+    }
+}
+
+/// Which volume setting a sound follows. Put it next to the `AudioBundle`.
+/// Sounds without one are treated as effects.
+#[derive(Component, Debug, Clone, Copy, Reflect, PartialEq, Eq, Default)]
+pub enum AudioKind {
+    Music,
+    #[default]
+    Effects,
+}
+
+/// Deaths in one progression node (see `CurrentNode`)
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RoomDeaths {
+    pub node: String,
+    pub deaths: u32,
+}
+
+/// Things that happened during the run
+#[derive(Debug, Clone, Default, Reflect, Serialize, Deserialize)]
+pub struct RunStats {
+    pub deaths: Vec<RoomDeaths>,
+    /// Deaths we can't tie to a node: in rooms outside the progression graph, or from saves that didn't
+    /// record nodes. They only count towards the total.
+    #[serde(default)]
+    pub other_deaths: u32,
+}
+impl RunStats {
+    pub fn deaths_in(&self, node: Option<&str>) -> u32 {
+        let Some(node) = node else {
+            return self.other_deaths;
+        };
+        self.deaths
+            .iter()
+            .find(|room| room.node == node)
+            .map(|room| room.deaths)
+            .unwrap_or(0)
+    }
+
+    pub fn total_deaths(&self) -> u32 {
+        self.deaths.iter().map(|room| room.deaths).sum::<u32>() + self.other_deaths
+    }
+
+    fn add_death(&mut self, node: Option<&str>) {
+        let Some(node) = node else {
+            self.other_deaths += 1;
+            return;
+        };
+        match self.deaths.iter_mut().find(|room| room.node == node) {
+            Some(room) => room.deaths += 1,
+            None => self.deaths.push(RoomDeaths {
+                node: node.to_string(),
+                deaths: 1,
+            }),
+        }
+    }
+}
+
+/// Everything needed to pick a run back up
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RunSave {
+    /// The asset path of the room file being played (if any). Generated rooms don't have one.
+    pub room_file: Option<String>,
+    /// Id of the progression node being played (see `CurrentNode`)
+    #[serde(default)]
+    pub node: Option<String>,
+    /// What the room was generated from, if it was generated
+    #[serde(default)]
+    pub room_seed: Option<u64>,
+    pub room_size: UVec2,
+    pub encounter_kind: EncounterKind,
+    pub difficulty: u32,
+    #[serde(default)]
+    pub stats: RunStats,
+    /// Ids of the upgrades taken (see `UpgradeDef`), in the order they were picked
+    #[serde(default)]
+    pub upgrades: Vec<String>,
+}
+impl RunSave {
+    pub fn new(
+        room_state: &RoomState,
+        room_file: Option<String>,
+        current_node: &CurrentNode,
+        stats: RunStats,
+        upgrades: Vec<String>,
+    ) -> Self {
+        Self {
+            room_file,
+            node: current_node.get_id().map(str::to_string),
+            room_seed: current_node.get_room_seed(),
+            room_size: room_state.room_size,
+            encounter_kind: room_state.encounter_state.kind,
+            difficulty: room_state.encounter_state.difficulty,
+            stats,
+            upgrades,
+        }
+    }
+
+    /// The room to resume in. You always start at the beginning of the encounter.
+    pub fn room_state(&self) -> RoomState {
+        RoomState::xth_encounter(self.encounter_kind, self.difficulty)
+            .with_room_size(self.room_size)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    run: Option<RunSave>,
+    settings: GameSettings,
+}
+
+/// Version 0: from before saves had a version, when only the run was saved
+#[derive(Deserialize)]
+struct SaveFileV0 {
+    run: Option<RunSaveV1>,
+}
+impl SaveFileV0 {
+    fn upgrade(self) -> SaveFileV1 {
+        SaveFileV1 {
+            run: self.run,
+            settings: default(),
+        }
+    }
+}
+
+/// Version 1: deaths were counted per encounter instead of per progression node
+#[derive(Deserialize)]
+struct SaveFileV1 {
+    run: Option<RunSaveV1>,
+    settings: GameSettings,
+}
+impl SaveFileV1 {
+    fn upgrade(self) -> SaveFile {
+        SaveFile {
+            version: 2,
+            run: self.run.map(RunSaveV1::upgrade),
+            settings: self.settings,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RunSaveV1 {
+    room_file: Option<String>,
+    room_size: UVec2,
+    encounter_kind: EncounterKind,
+    difficulty: u32,
+    #[serde(default)]
+    stats: RunStatsV1,
+}
+impl RunSaveV1 {
+    fn upgrade(self) -> RunSave {
+        // There's no telling which node an encounter was, so the old deaths only count towards the total
+        let other_deaths = self.stats.deaths.iter().map(|room| room.deaths).sum();
+        RunSave {
+            room_file: self.room_file,
+            node: None,
+            room_seed: None,
+            room_size: self.room_size,
+            encounter_kind: self.encounter_kind,
+            difficulty: self.difficulty,
+            stats: RunStats {
+                deaths: vec![],
+                other_deaths,
+            },
+            upgrades: vec![],
+        }
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct RunStatsV1 {
+    deaths: Vec<RoomDeathsV1>,
+}
+
+#[derive(Deserialize)]
+struct RoomDeathsV1 {
+    encounter_kind: EncounterKind,
+    difficulty: u32,
+    deaths: u32,
+}
+
+/// Just enough of a save file to figure out how to read the rest of it
+#[derive(Deserialize)]
+struct SaveFileVersion {
+    #[serde(default)]
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Write(ron::Error),
+    /// Saved by a newer version of the game than this one
+    FromTheFuture(u32),
+    /// A version we don't know how to migrate from
+    Unsupported(u32),
+}
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't access save file: {err}"),
+            Self::Parse(err) => write!(f, "couldn't parse save file: {err}"),
+            Self::Write(err) => write!(f, "couldn't serialize save file: {err}"),
+            Self::FromTheFuture(version) => write!(
+                f,
+                "save file is version {version}, but this build only understands up to {SAVE_VERSION}"
+            ),
+            Self::Unsupported(version) => {
+                write!(f, "don't know how to migrate save file version {version}")
+            }
+        }
+    }
+}
+impl std::error::Error for SaveError {}
+impl From<std::io::Error> for SaveError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Parse(value)
+    }
+}
+impl From<ron::Error> for SaveError {
+    fn from(value: ron::Error) -> Self {
+        Self::Write(value)
+    }
+}
+
+/// Turns the contents of a save file (of any supported version) into the current format
+fn migrate(text: &str) -> Result<SaveFile, SaveError> {
+    let SaveFileVersion { version } = ron::from_str(text)?;
+    match version {
+        0 => Ok(ron::from_str::<SaveFileV0>(text)?.upgrade().upgrade()),
+        1 => Ok(ron::from_str::<SaveFileV1>(text)?.upgrade()),
+        SAVE_VERSION => Ok(ron::from_str(text)?),
+        version if version > SAVE_VERSION => Err(SaveError::FromTheFuture(version)),
+        version => Err(SaveError::Unsupported(version)),
+    }
+}
+
+/// Where save data lives on this platform
+fn save_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "android")]
+    {
+        bevy::winit::ANDROID_APP
+            .get()
+            .and_then(|android_app| android_app.internal_data_path())
+    }
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("APPDATA").map(|dir| PathBuf::from(dir).join(APP_DIR_NAME))
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join("Library")
+                .join("Application Support")
+                .join(APP_DIR_NAME)
+        })
+    }
+    #[cfg(not(any(
+        target_os = "android",
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "ios"
+    )))]
+    {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(".local").join("share"))
+            })
+            .map(|dir| dir.join(APP_DIR_NAME))
+    }
+}
+
+fn save_path() -> Option<PathBuf> {
+    save_dir().map(|dir| dir.join(SAVE_FILE_NAME))
+}
+
+fn read_save_file() -> Result<Option<SaveFile>, SaveError> {
+    let Some(path) = save_path() else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text = std::fs::read_to_string(&path)?;
+    let save_file = migrate(&text);
+    if save_file.is_err() {
+        // Whatever this is, the next save would overwrite it. Keep a copy in case a newer build can read it.
+        let backup = path.with_file_name(BACKUP_FILE_NAME);
+        match std::fs::copy(&path, &backup) {
+            Ok(_) => info!("Backed up unreadable save file to {}", backup.display()),
+            Err(err) => warn!("Couldn't back up unreadable save file: {err}"),
+        }
+    }
+    save_file.map(Some)
+}
+
+fn write_save_file(save_file: &SaveFile) -> Result<(), SaveError> {
+    let Some(path) = save_path() else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let text = ron::ser::to_string_pretty(save_file, ron::ser::PrettyConfig::default())?;
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+/// The saved state of the current run. `None` means there's nothing to continue.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct SavedRun {
+    run: Option<RunSave>,
+}
+impl SavedRun {
+    pub fn get_run(&self) -> Option<&RunSave> {
+        self.run.as_ref()
+    }
+
+    pub fn can_continue(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Stats for the current run (empty if there isn't one)
+    pub fn get_stats(&self) -> RunStats {
+        self.run
+            .as_ref()
+            .map(|run| run.stats.clone())
+            .unwrap_or_default()
+    }
+
+    /// Upgrades taken this run (empty if there isn't one)
+    pub fn get_upgrades(&self) -> &[String] {
+        self.run
+            .as_ref()
+            .map(|run| run.upgrades.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Takes an upgrade for the rest of the run. Does nothing if there's no run going.
+    pub fn add_upgrade(&mut self, id: &str) {
+        if let Some(run) = self.run.as_mut() {
+            run.upgrades.push(id.to_string());
+        }
+    }
+
+    /// Forgets the current run (i.e. when starting a new game)
+    pub fn clear(&mut self) {
+        self.run = None;
+    }
+}
+
+/// Send this to write the save file at the end of the frame
+#[derive(Event, Debug, Clone)]
+pub struct WriteSave;
+
+/// Every time a room starts, that's where you'd continue from
+fn record_run(
+    meta_state: Res<State<MetaState>>,
+    active_room_file: Res<ActiveRoomFile>,
+    current_node: Res<CurrentNode>,
+    mut saved_run: ResMut<SavedRun>,
+    mut writes: EventWriter<WriteSave>,
+) {
+    let Some(room_state) = meta_state.get_room_state() else {
+        return;
+    };
+    // The room file might not be picked yet (i.e. on the very first frame), in which case it's the same as before.
+    // A generated room is picked but has no path, and is rebuilt from `current_node` instead.
+    let room_file = match active_room_file.get_handle() {
+        Some(_) => active_room_file.get_path(),
+        None => saved_run.get_run().and_then(|run| run.room_file.clone()),
+    };
+    let stats = saved_run.get_stats();
+    let upgrades = saved_run.get_upgrades().to_vec();
+    saved_run.run = Some(RunSave::new(
+        &room_state,
+        room_file,
+        &current_node,
+        stats,
+        upgrades,
+    ));
+    writes.send(WriteSave);
+}
+
+fn record_death(
+    current_node: Res<CurrentNode>,
+    mut saved_run: ResMut<SavedRun>,
+    mut writes: EventWriter<WriteSave>,
+) {
+    let Some(run) = saved_run.run.as_mut() else {
+        return;
+    };
+    run.stats.add_death(current_node.get_id());
+    writes.send(WriteSave);
+}
+
+fn watch_settings(settings: Res<GameSettings>, mut writes: EventWriter<WriteSave>) {
+    if settings.is_changed() && !settings.is_added() {
+        writes.send(WriteSave);
+    }
+}
+
+fn watch_app_exit(mut exits: EventReader<AppExit>, mut writes: EventWriter<WriteSave>) {
+    if exits.read().next().is_some() {
+        writes.send(WriteSave);
+    }
+}
+
+fn write_save(
+    mut writes: EventReader<WriteSave>,
+    saved_run: Res<SavedRun>,
+    settings: Res<GameSettings>,
+) {
+    // No matter how many things asked, only write once
+    if writes.read().count() == 0 {
+        return;
+    }
+    let save_file = SaveFile {
+        version: SAVE_VERSION,
+        run: saved_run.run.clone(),
+        settings: settings.clone(),
+    };
+    if let Err(err) = write_save_file(&save_file) {
+        warn!("Couldn't save: {err}");
+    }
+}
+
+/// The save is read immediately, so the title screen knows whether there's anything to continue
+pub(super) struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<GameSettings>();
+        app.register_type::<AudioKind>();
+        app.register_type::<SavedRun>();
+        app.add_event::<WriteSave>();
+
+        let save_file = match read_save_file() {
+            Ok(save_file) => save_file,
+            Err(err) => {
+                warn!("Ignoring save file: {err}");
+                None
+            }
+        };
+        match save_file {
+            Some(save_file) => {
+                app.insert_resource(SavedRun { run: save_file.run });
+                app.insert_resource(save_file.settings);
+            }
+            None => {
+                app.insert_resource(SavedRun::default());
+                app.insert_resource(GameSettings::default());
+            }
+        }
+        debug_resource!(app, GameSettings);
+
+        app.add_systems(OnEnter(EncounterProgress::Entering), record_run);
+        app.add_systems(OnEnter(EncounterProgress::Dead), record_death);
+        app.add_systems(Last, (watch_settings, watch_app_exit, write_save).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V0_SAVE: &str = r#"(
+        run: Some((
+            room_file: Some("rooms/debug.room.ron"),
+            room_size: (640, 360),
+            encounter_kind: SimpOnly,
+            difficulty: 1,
+        )),
+    )"#;
+
+    const V1_SAVE: &str = r#"(
+        version: 1,
+        run: Some((
+            room_file: Some("rooms/debug.room.ron"),
+            room_size: (640, 360),
+            encounter_kind: Both,
+            difficulty: 2,
+            stats: (
+                deaths: [
+                    (encounter_kind: SimpOnly, difficulty: 1, deaths: 3),
+                    (encounter_kind: Both, difficulty: 2, deaths: 2),
+                ],
+            ),
+        )),
+        settings: (
+            music_volume: 0.5,
+            effects_volume: 0.25,
+        ),
+    )"#;
+
+    #[test]
+    fn migrates_v0() {
+        let save = migrate(V0_SAVE).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        let run = save.run.unwrap();
+        assert_eq!(run.room_file.as_deref(), Some("rooms/debug.room.ron"));
+        assert_eq!(run.node, None);
+        assert_eq!(run.room_size, UVec2::new(640, 360));
+        assert_eq!(run.encounter_kind, EncounterKind::SimpOnly);
+        assert_eq!(run.difficulty, 1);
+        assert_eq!(run.stats.total_deaths(), 0);
+        assert!(run.upgrades.is_empty());
+        assert_eq!(save.settings.music_volume, 1.0);
+        assert_eq!(save.settings.effects_volume, 1.0);
+    }
+
+    #[test]
+    fn migrates_v1() {
+        let save = migrate(V1_SAVE).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        let run = save.run.unwrap();
+        assert_eq!(run.room_file.as_deref(), Some("rooms/debug.room.ron"));
+        assert_eq!(run.node, None);
+        assert_eq!(run.room_seed, None);
+        assert_eq!(run.encounter_kind, EncounterKind::Both);
+        assert_eq!(run.difficulty, 2);
+        assert_eq!(save.settings.music_volume, 0.5);
+        assert_eq!(save.settings.effects_volume, 0.25);
+    }
+
+    #[test]
+    fn v1_deaths_only_count_towards_total() {
+        let stats = migrate(V1_SAVE).unwrap().run.unwrap().stats;
+        assert!(stats.deaths.is_empty());
+        assert_eq!(stats.deaths_in(None), 5);
+        assert_eq!(stats.total_deaths(), 5);
+    }
+
+    #[test]
+    fn current_version_round_trips() {
+        let mut stats = RunStats::default();
+        stats.add_death(Some("fight1"));
+        stats.add_death(Some("fight1"));
+        stats.add_death(None);
+        let save = SaveFile {
+            version: SAVE_VERSION,
+            run: Some(RunSave {
+                room_file: None,
+                node: Some("fight1".to_string()),
+                room_seed: Some(7),
+                room_size: UVec2::new(640, 360),
+                encounter_kind: EncounterKind::SpewOnly,
+                difficulty: 3,
+                stats,
+                upgrades: vec!["bigger_engine".to_string()],
+            }),
+            settings: default(),
+        };
+        let text = ron::ser::to_string_pretty(&save, default()).unwrap();
+        let run = migrate(&text).unwrap().run.unwrap();
+        assert_eq!(run.node.as_deref(), Some("fight1"));
+        assert_eq!(run.room_seed, Some(7));
+        assert_eq!(run.stats.deaths_in(Some("fight1")), 2);
+        assert_eq!(run.stats.deaths_in(None), 1);
+        assert_eq!(run.upgrades, vec!["bigger_engine".to_string()]);
+    }
+
+    #[test]
+    fn rejects_future_versions() {
+        let text = format!("(version: {}, run: None)", SAVE_VERSION + 1);
+        assert!(matches!(
+            migrate(&text),
+            Err(SaveError::FromTheFuture(version)) if version == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(matches!(migrate("not a save"), Err(SaveError::Parse(_))));
+    }
+}
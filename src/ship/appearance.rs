@@ -0,0 +1,183 @@
+//! Picks the ship's animation state from what's going on with it. Nothing else should set
+//! `AnimationManager<AnimationShip>` states by hand, it all comes from components:
+//! - `Dying`: plays the damage animation once, then hides
+//! - `DamageFlash` (see `health.rs`): damage animation
+//! - `Stuck`: idle
+//! - Launch cooldown (see `LaunchCharges`): launch animation
+//! - Otherwise: cruising
+//!
+//! The gun and tail are rig parts (see `rig.rs`). The gun is out while cruising or idle, and turns towards
+//! wherever a shot is being aimed (right drag). The tail is only out while cruising (it's tucked in while
+//! idle, along with a dimmer light), and lags behind the velocity.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct AppearanceConstants {
+    /// Radians per second
+    gun_turn_speed: f32,
+    /// Radians per second, slow so it swings around behind the ship
+    tail_turn_speed: f32,
+    /// Below this speed the tail just relaxes back to the ship
+    tail_min_speed: f32,
+}
+impl Default for AppearanceConstants {
+    fn default() -> Self {
+        Self {
+            gun_turn_speed: 18.0,
+            tail_turn_speed: 5.0,
+            tail_min_speed: 10.0,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct ShipGun;
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct ShipTail;
+
+fn attach_ship_rigs(
+    new_ships: Query<Entity, Added<Ship>>,
+    constants: Res<AppearanceConstants>,
+    mut commands: Commands,
+) {
+    for eid in &new_ships {
+        commands.entity(eid).with_children(|ship| {
+            ship.spawn((
+                ShipTail,
+                RigPartBundle::<AnimationShipTail>::new(
+                    "ship_tail",
+                    RigPart::new(Vec2::ZERO, Vec2::ZERO)
+                        .with_z(-0.1)
+                        .with_turn_speed(constants.tail_turn_speed),
+                ),
+            ));
+            ship.spawn((
+                ShipGun,
+                RigPartBundle::<AnimationShipGun>::new(
+                    "ship_gun",
+                    RigPart::new(Vec2::ZERO, Vec2::ZERO)
+                        .with_z(0.1)
+                        .with_turn_speed(constants.gun_turn_speed),
+                ),
+            ));
+        });
+    }
+}
+
+fn update_ship_appearance(
+    mut ships: Query<
+        (
+            &mut AnimationManager<AnimationShip>,
+            Option<&LaunchCharges>,
+            Has<Dying>,
+            Has<DamageFlash>,
+            Has<Stuck>,
+        ),
+        With<Ship>,
+    >,
+) {
+    for (mut animation, charges, dying, damaged, stuck) in &mut ships {
+        let launching = charges.is_some_and(|charges| charges.is_cooling_down());
+        let state = if dying {
+            AnimationShip::Dying
+        } else if damaged {
+            AnimationShip::Damage
+        } else if stuck {
+            AnimationShip::Idle
+        } else if launching {
+            AnimationShip::Launch
+        } else {
+            AnimationShip::Cruise
+        };
+        animation.set_state(state);
+    }
+}
+
+/// Stows the parts whenever the ship isn't showing its body (and the tail while idle), and points them
+fn update_ship_rigs(
+    ships: Query<(&AnimationManager<AnimationShip>, &DynoTran), With<Ship>>,
+    mut guns: Query<
+        (
+            &Parent,
+            &mut RigPart,
+            &mut AnimationManager<AnimationShipGun>,
+        ),
+        (With<ShipGun>, Without<ShipTail>),
+    >,
+    mut tails: Query<
+        (
+            &Parent,
+            &mut RigPart,
+            &mut AnimationManager<AnimationShipTail>,
+        ),
+        (With<ShipTail>, Without<ShipGun>),
+    >,
+    mut fires: EventReader<Fire>,
+    drag_input: Res<DragInput>,
+    constants: Res<AppearanceConstants>,
+) {
+    let gun_out = |animation: &AnimationManager<AnimationShip>| {
+        matches!(
+            animation.get_state(),
+            AnimationShip::Cruise | AnimationShip::Idle
+        )
+    };
+    let tail_out = |animation: &AnimationManager<AnimationShip>| {
+        animation.get_state() == AnimationShip::Cruise
+    };
+    // Keep pointing wherever the last shot went once the drag is over
+    let last_fire = fires.read().last().map(|fire| fire.0);
+    let aim = drag_input
+        .get_right_drag_start()
+        .map(|drag_start| drag_start - drag_input.get_world_pos())
+        .or(last_fire)
+        .filter(|aim| *aim != Vec2::ZERO);
+    for (parent, mut part, mut animation) in &mut guns {
+        let Ok((ship_animation, _)) = ships.get(parent.get()) else {
+            continue;
+        };
+        animation.set_state(if gun_out(ship_animation) {
+            AnimationShipGun::Out
+        } else {
+            AnimationShipGun::Stowed
+        });
+        if let Some(aim) = aim {
+            part.set_target(Some(aim.to_angle()));
+        }
+    }
+    for (parent, mut part, mut animation) in &mut tails {
+        let Ok((ship_animation, dyno_tran)) = ships.get(parent.get()) else {
+            continue;
+        };
+        animation.set_state(if tail_out(ship_animation) {
+            AnimationShipTail::Out
+        } else {
+            AnimationShipTail::Stowed
+        });
+        // The tail is drawn off the back (-x) of the ship, so facing along the velocity puts it behind
+        let target =
+            (dyno_tran.vel.length() > constants.tail_min_speed).then(|| dyno_tran.vel.to_angle());
+        if part.get_target() != target {
+            part.set_target(target);
+        }
+    }
+}
+
+pub(super) fn register_appearance(app: &mut App) {
+    app.register_type::<ShipGun>();
+    app.register_type::<ShipTail>();
+    app.insert_resource(AppearanceConstants::default());
+    debug_resource!(app, AppearanceConstants);
+
+    app.add_systems(
+        Update,
+        (attach_ship_rigs, update_ship_appearance, update_ship_rigs)
+            .chain()
+            .after(PhysicsSet)
+            .after(HealthSet)
+            .before(AnimationSet)
+            .before(RigSet),
+    );
+}
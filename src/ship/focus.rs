@@ -0,0 +1,178 @@
+//! Focus: time slows down on its own while the player is aiming (holding either drag).
+//! It runs on a meter (`BulletTimeMeter`) that drains while focusing and comes back on its own after a short
+//! break, or right away in chunks for every kill. A bigger `bullet_time_duration` stat means a bigger meter.
+//!
+//! Going in and out of slow motion ramps smoothly. It only ever touches the aim factor of `BulletTime`, so
+//! it doesn't fight with anything else that slows time down.
+//! NOTE: The meter is measured in real seconds, not in-game ones (those are the slowed down ones).
+//! Focus only happens while physics is running, and never while a conversation is open.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct FocusConstants {
+    /// How long a full meter lasts with the base `bullet_time_duration`
+    base_capacity: f32,
+    /// Time factor when all the way focused
+    slow_factor: f32,
+    /// How long it takes to go all the way into (or out of) slow motion
+    ramp_time: f32,
+    /// How long after focusing before the meter starts coming back
+    regen_delay: f32,
+    /// Fraction of the full meter that comes back every second
+    regen_rate: f32,
+    /// Fraction of the full meter that comes back for every kill
+    kill_refill: f32,
+}
+impl Default for FocusConstants {
+    fn default() -> Self {
+        Self {
+            base_capacity: 2.0,
+            slow_factor: 0.25,
+            ramp_time: 0.15,
+            regen_delay: 0.75,
+            regen_rate: 0.2,
+            kill_refill: 0.25,
+        }
+    }
+}
+
+/// How much slow motion is left. The HUD should read this.
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct BulletTimeMeter {
+    /// Seconds left
+    amount: f32,
+    /// Seconds when full
+    capacity: f32,
+    /// How far into slow motion we are, 0 is normal time and 1 is all the way slow
+    ramp: f32,
+    regen_cooldown: f32,
+}
+impl BulletTimeMeter {
+    impl_get!(amount, f32);
+    impl_get!(capacity, f32);
+    impl_get!(ramp, f32);
+
+    /// How full the meter is, between 0 and 1
+    pub fn get_fraction(&self) -> f32 {
+        if self.capacity <= 0.0 {
+            0.0
+        } else {
+            self.amount / self.capacity
+        }
+    }
+
+    pub fn is_focusing(&self) -> bool {
+        self.ramp > 0.0
+    }
+
+    fn refill(&mut self, fraction: f32) {
+        self.amount = (self.amount + self.capacity * fraction).min(self.capacity);
+    }
+}
+impl Default for BulletTimeMeter {
+    fn default() -> Self {
+        let capacity = FocusConstants::default().base_capacity;
+        Self {
+            amount: capacity,
+            capacity,
+            ramp: 0.0,
+            regen_cooldown: 0.0,
+        }
+    }
+}
+
+fn update_focus(
+    ships: Query<(), (With<Ship>, Without<Dying>)>,
+    drag_input: Res<DragInput>,
+    stats: Res<ShipStats>,
+    constants: Res<FocusConstants>,
+    time: Res<Time>,
+    active_convo: Res<ActiveConvo>,
+    mut meter: ResMut<BulletTimeMeter>,
+    mut bullet_time: ResMut<BulletTime>,
+) {
+    if bullet_time.is_paused() {
+        return;
+    }
+    let dt = time.delta_seconds();
+    meter.capacity = constants.base_capacity * stats.bullet_time_duration;
+    meter.amount = meter.amount.min(meter.capacity);
+    let aiming =
+        drag_input.get_left_drag_start().is_some() || drag_input.get_right_drag_start().is_some();
+    // Dragging through a conversation (i.e. picking a choice) isn't aiming
+    let focusing = aiming && !ships.is_empty() && !active_convo.is_busy() && meter.amount > 0.0;
+    if focusing {
+        meter.amount = (meter.amount - dt).max(0.0);
+        meter.regen_cooldown = constants.regen_delay;
+    } else if meter.regen_cooldown > 0.0 {
+        meter.regen_cooldown = (meter.regen_cooldown - dt).max(0.0);
+    } else {
+        meter.refill(constants.regen_rate * dt);
+    }
+    let ramp_step = if constants.ramp_time > 0.0 {
+        dt / constants.ramp_time
+    } else {
+        1.0
+    };
+    meter.ramp = if focusing {
+        (meter.ramp + ramp_step).min(1.0)
+    } else {
+        (meter.ramp - ramp_step).max(0.0)
+    };
+    bullet_time.set_aim_factor(Spleen::EaseInOutQuad.bound_interp(
+        meter.ramp,
+        1.0,
+        constants.slow_factor,
+    ));
+}
+
+/// Every enemy killed (as opposed to cleaned up or blowing itself up) gives some of the meter back
+fn refill_on_kills(
+    deaths: Query<(&Dying, &TriggerRx), Added<Dying>>,
+    constants: Res<FocusConstants>,
+    mut meter: ResMut<BulletTimeMeter>,
+) {
+    for (dying, rx) in &deaths {
+        if rx.kind == TriggerKind::Enemy && matches!(dying.get_cause(), DeathCause::Damage { .. }) {
+            meter.refill(constants.kill_refill);
+        }
+    }
+}
+
+/// Drops out of slow motion right away, but keeps what's left of the meter (pausing shouldn't refill it)
+fn release_focus(mut meter: ResMut<BulletTimeMeter>, mut bullet_time: ResMut<BulletTime>) {
+    meter.ramp = 0.0;
+    bullet_time.set_aim_factor(1.0);
+}
+
+/// Each room (and tutorial) starts with a full meter and normal time
+fn reset_focus(mut meter: ResMut<BulletTimeMeter>, mut bullet_time: ResMut<BulletTime>) {
+    *meter = BulletTimeMeter {
+        amount: meter.capacity,
+        capacity: meter.capacity,
+        ..default()
+    };
+    bullet_time.set_aim_factor(1.0);
+}
+
+pub(super) fn register_focus(app: &mut App) {
+    app.register_type::<BulletTimeMeter>();
+    app.insert_resource(FocusConstants::default());
+    debug_resource!(app, FocusConstants);
+    app.insert_resource(BulletTimeMeter::default());
+
+    app.add_systems(
+        Update,
+        (refill_on_kills, update_focus)
+            .chain()
+            .after(InputSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
+    app.add_systems(OnEnter(MetaStateKind::Room), reset_focus);
+    app.add_systems(OnExit(MetaStateKind::Room), reset_focus);
+    app.add_systems(OnEnter(MetaStateKind::Tutorial), reset_focus);
+    app.add_systems(OnExit(MetaStateKind::Tutorial), reset_focus);
+    // `update_focus` stops running, so it can't ramp back out on its own
+    app.add_systems(OnExit(PhysicsState::Active), release_focus);
+}
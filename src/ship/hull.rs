@@ -0,0 +1,121 @@
+//! The ship's hit points, on top of the shared `Health` (see `health.rs`). Anything with a `Damage` hitbox
+//! that hurts `TriggerKind::Ship` can hurt it. After taking a hit the ship can't be hurt again for a moment.
+//! When the hull runs out the ship blows up: an explosion, a moment of slow motion, and then it's gone
+//! (through `Dying`). The director notices and moves the encounter to `Dead`.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct ShipHullConstants {
+    max_hp: u32,
+    invincible_time: f32,
+    /// How long the ship spends dying (in-game time, so it's stretched by the slow motion)
+    death_time: f32,
+    /// Bullet time factor while the ship is dying
+    death_time_factor: f32,
+}
+impl Default for ShipHullConstants {
+    fn default() -> Self {
+        Self {
+            max_hp: 3,
+            invincible_time: 1.0,
+            death_time: 0.4,
+            death_time_factor: 0.2,
+        }
+    }
+}
+
+#[derive(Bundle)]
+struct ShipExplosionBundle {
+    name: Name,
+    spatial: SpatialBundle,
+    animation: AnimationManager<AnimationShipExplosion>,
+}
+impl ShipExplosionBundle {
+    fn new(pos: Vec2) -> Self {
+        Self {
+            name: Name::new("ship_explosion"),
+            spatial: spat_tran!(pos.x, pos.y, ZIX_SHIP),
+            animation: AnimationManager::new(),
+        }
+    }
+}
+
+/// Base hit points plus (or minus) upgrades. Never less than one, so the ship can't spawn dead.
+fn max_hp(constants: &ShipHullConstants, stats: &ShipStats) -> u32 {
+    (constants.max_hp as i32 + stats.extra_hp()).max(1) as u32
+}
+
+/// Ships get their hull when they show up, so every ship bundle starts fresh
+fn attach_hulls(
+    new_ships: Query<Entity, (Added<Ship>, Without<Health>)>,
+    constants: Res<ShipHullConstants>,
+    stats: Res<ShipStats>,
+    mut commands: Commands,
+) {
+    for eid in &new_ships {
+        commands.entity(eid).insert(
+            Health::new(max_hp(&constants, &stats))
+                .with_invulnerable_time(constants.invincible_time)
+                .with_death_time(constants.death_time),
+        );
+    }
+}
+
+/// Hull upgrades taken mid-room apply right away (and the new hit points come already repaired)
+fn upgrade_hulls(
+    stats: Res<ShipStats>,
+    constants: Res<ShipHullConstants>,
+    mut healths: Query<&mut Health, With<Ship>>,
+) {
+    if !stats.is_changed() {
+        return;
+    }
+    for mut health in &mut healths {
+        health.set_max_hp(max_hp(&constants, &stats));
+    }
+}
+
+/// Once the hull runs out (see `apply_damage`), the ship goes out with a bang
+fn blow_up_ships(
+    mut ships: Query<(&Dying, &mut DynoTran, &GlobalTransform), (With<Ship>, Added<Dying>)>,
+    constants: Res<ShipHullConstants>,
+    mut bullet_time: ResMut<BulletTime>,
+    room_root: Res<RoomRoot>,
+    mut commands: Commands,
+) {
+    for (dying, mut dyno_tran, gtran) in &mut ships {
+        if !matches!(dying.get_cause(), DeathCause::Damage { .. }) {
+            continue;
+        }
+        dyno_tran.vel = Vec2::ZERO;
+        bullet_time.set_time_factor(constants.death_time_factor);
+        commands
+            .spawn(ShipExplosionBundle::new(gtran.pos_n_angle().0))
+            .set_parent(room_root.eid());
+    }
+}
+
+/// Once the ship is gone (or we leave the room mid-explosion), time goes back to normal
+fn end_death_slowmo(mut bullet_time: ResMut<BulletTime>) {
+    bullet_time.set_normal();
+}
+
+pub(super) fn register_hull(app: &mut App) {
+    app.insert_resource(ShipHullConstants::default());
+    debug_resource!(app, ShipHullConstants);
+
+    app.add_systems(
+        Update,
+        (
+            (attach_hulls, upgrade_hulls).chain().before(HealthSet),
+            blow_up_ships.after(HealthSet),
+        )
+            .after(PhysicsSet)
+            .run_if(in_state(PhysicsState::Active))
+            // Tutorials are forgiving
+            .run_if(in_state(MetaStateKind::Room)),
+    );
+    app.add_systems(OnEnter(EncounterProgress::Dead), end_death_slowmo);
+    app.add_systems(OnExit(MetaStateKind::Room), end_death_slowmo);
+}
@@ -0,0 +1,162 @@
+//! Turns `Launch` input into the ship actually going somewhere.
+//! A launch uses up a charge. Charges only come back while the ship is stuck to something, so you can't
+//! chain launches forever in open space. There's also a short cooldown so one flick can't double launch.
+//!
+//! Speed follows a curve on how far you dragged (gentle drags give fine control) up to a cap, and then gets
+//! scaled by the `launch_power` upgrade stat.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+pub(super) struct LaunchConstants {
+    /// Drags shorter than this are ignored (they were probably just clicks)
+    min_drag: f32,
+    /// Dragging further than this doesn't launch any faster
+    max_drag: f32,
+    min_speed: f32,
+    max_speed: f32,
+    /// How far along the drag is gets raised to this power. Bigger means more room for gentle launches.
+    power_curve: f32,
+    max_charges: u32,
+    /// How long the ship has to be stuck to get a charge back
+    recharge_time: f32,
+    cooldown: f32,
+}
+impl Default for LaunchConstants {
+    fn default() -> Self {
+        Self {
+            min_drag: 4.0,
+            max_drag: 100.0,
+            min_speed: 40.0,
+            max_speed: 240.0,
+            power_curve: 1.5,
+            max_charges: 2,
+            recharge_time: 0.4,
+            cooldown: 0.2,
+        }
+    }
+}
+impl LaunchConstants {
+    /// How fast a drag this long sends the ship (before upgrades)
+    fn speed_for_drag(&self, drag: f32) -> f32 {
+        let frac = ((drag - self.min_drag) / (self.max_drag - self.min_drag)).clamp(0.0, 1.0);
+        self.min_speed + (self.max_speed - self.min_speed) * frac.powf(self.power_curve)
+    }
+
+    /// The velocity a launch drag gives the ship, or `None` if it's too short to count.
+    /// The trajectory preview (see `trajectory.rs`) uses this too, so it always matches.
+    pub(super) fn launch_vel(&self, drag: Vec2, stats: &ShipStats) -> Option<Vec2> {
+        let length = drag.length();
+        if length < self.min_drag {
+            return None;
+        }
+        let speed = self.speed_for_drag(length) * stats.launch_power;
+        Some(drag / length * speed)
+    }
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct LaunchCharges {
+    charges: u32,
+    max_charges: u32,
+    /// Time spent stuck towards the next charge
+    recharge: f32,
+    cooldown: f32,
+}
+impl LaunchCharges {
+    pub fn new(max_charges: u32) -> Self {
+        Self {
+            charges: max_charges,
+            max_charges,
+            recharge: 0.0,
+            cooldown: 0.0,
+        }
+    }
+
+    impl_get!(charges, u32);
+    impl_get!(max_charges, u32);
+
+    pub fn can_launch(&self) -> bool {
+        self.charges > 0 && self.cooldown <= 0.0
+    }
+
+    /// True for a moment right after launching
+    pub fn is_cooling_down(&self) -> bool {
+        self.cooldown > 0.0
+    }
+}
+
+/// Ships get their charges when they show up, so every ship bundle starts fresh
+fn attach_launch_charges(
+    new_ships: Query<Entity, (Added<Ship>, Without<LaunchCharges>)>,
+    constants: Res<LaunchConstants>,
+    mut commands: Commands,
+) {
+    for eid in &new_ships {
+        commands
+            .entity(eid)
+            .insert(LaunchCharges::new(constants.max_charges));
+    }
+}
+
+fn update_launch_charges(
+    mut ships: Query<(&mut LaunchCharges, Option<&Stuck>)>,
+    constants: Res<LaunchConstants>,
+    bullet_time: Res<BulletTime>,
+) {
+    let dt = bullet_time.delta_seconds();
+    for (mut charges, stuck) in &mut ships {
+        charges.cooldown = (charges.cooldown - dt).max(0.0);
+        if stuck.is_none() || charges.charges >= charges.max_charges {
+            charges.recharge = 0.0;
+            continue;
+        }
+        charges.recharge += dt;
+        if charges.recharge >= constants.recharge_time {
+            charges.recharge = 0.0;
+            charges.charges += 1;
+        }
+    }
+}
+
+fn launch_ships(
+    mut launches: EventReader<Launch>,
+    mut ships: Query<
+        (Entity, &mut LaunchCharges, &mut DynoTran, &mut Transform),
+        (With<Ship>, Without<Dying>),
+    >,
+    constants: Res<LaunchConstants>,
+    stats: Res<ShipStats>,
+    mut commands: Commands,
+) {
+    for launch in launches.read() {
+        let Some(vel) = constants.launch_vel(launch.0, &stats) else {
+            continue;
+        };
+        for (eid, mut charges, mut dyno_tran, mut tran) in &mut ships {
+            if !charges.can_launch() {
+                continue;
+            }
+            charges.charges -= 1;
+            charges.cooldown = constants.cooldown;
+            commands.entity(eid).remove::<Stuck>();
+            dyno_tran.vel = vel;
+            tran.set_angle(vel.to_angle());
+        }
+    }
+}
+
+pub(super) fn register_launch(app: &mut App) {
+    app.register_type::<LaunchCharges>();
+    app.insert_resource(LaunchConstants::default());
+    debug_resource!(app, LaunchConstants);
+
+    app.add_systems(
+        Update,
+        (attach_launch_charges, update_launch_charges, launch_ships)
+            .chain()
+            .after(InputSet)
+            .before(PhysicsSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
+}
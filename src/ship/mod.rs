@@ -1,5 +1,22 @@
 use crate::prelude::*;
 
+pub mod appearance;
+pub mod focus;
+pub mod hull;
+pub mod launch;
+pub mod projectile;
+pub mod trajectory;
+pub mod upgrades;
+pub mod weapons;
+
+pub use focus::*;
+pub use hull::*;
+pub use launch::*;
+pub use projectile::*;
+pub use trajectory::*;
+pub use upgrades::*;
+pub use weapons::*;
+
 #[derive(Component, Debug, Clone, Reflect)]
 pub struct Ship;
 
@@ -15,6 +32,7 @@ pub struct ShipBundle {
     animation: AnimationManager<AnimationShip>,
     camera_leader: DynamicCameraLeader,
     wrap_room: RoomWrap,
+    mirage: MirageAnimationManager,
 }
 impl ShipBundle {
     pub fn new(pos: Vec2, room_state: &RoomState) -> Self {
@@ -22,10 +40,6 @@ impl ShipBundle {
             center: Vec2::ZERO,
             radius: 6.0,
         };
-        let mut all_shapes = vec![shape.clone()];
-        for offset in room_state.mirage_offsets() {
-            all_shapes.push(shape.clone().with_offset(offset));
-        }
         Self {
             name: Name::new("ship"),
             spatial: spat_tran!(pos.x, pos.y, ZIX_SHIP),
@@ -38,11 +52,32 @@ impl ShipBundle {
                     radius: 6.0,
                 },
             ),
-            trigger_tx: TriggerTx::from_kind_n_shapes(TriggerKind::Ship, all_shapes),
+            trigger_tx: TriggerTx::from_kind_n_wrapped_shapes(
+                TriggerKind::Ship,
+                vec![shape.clone()],
+                room_state,
+            ),
             trigger_rx: TriggerRx::from_kind_n_shape(TriggerKind::Ship, shape),
             animation: AnimationManager::new(),
             camera_leader: DynamicCameraLeader,
             wrap_room: RoomWrap,
+            mirage: MirageAnimationManager::room_offsets(room_state),
         }
     }
 }
+
+pub(super) struct ShipPlugin;
+impl Plugin for ShipPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Ship>();
+
+        appearance::register_appearance(app);
+        focus::register_focus(app);
+        hull::register_hull(app);
+        launch::register_launch(app);
+        projectile::register_projectiles(app);
+        trajectory::register_trajectory(app);
+        upgrades::register_upgrades(app);
+        weapons::register_weapons(app);
+    }
+}
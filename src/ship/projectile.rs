@@ -0,0 +1,147 @@
+//! Things the ship shoots. A projectile flies in a straight line until it hits something or runs out of time.
+//! Its lifetime is just a `Dying` it's spawned with, so it goes away through the normal lifecycle.
+//!
+//! Projectiles stop on anything static (`StaticRxKind::Stop`) and carry a `TriggerKind::Bullet` hitbox that
+//! hurts enemies' hurtboxes (`TriggerKind::Enemy`, see `health.rs`). Either way, they burst into impact
+//! particles and die.
+
+use crate::prelude::*;
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct Projectile {
+    /// Set once it's hit something, so it only hits once
+    spent: bool,
+}
+
+#[derive(Bundle)]
+pub struct ProjectileBundle {
+    name: Name,
+    projectile: Projectile,
+    sprite: SpriteBundle,
+    render_layers: RenderLayers,
+    dyno_tran: DynoTran,
+    static_rx: StaticRx,
+    trigger_tx: TriggerTx,
+    damage: Damage,
+    wrap: RoomWrap,
+    dying: Dying,
+}
+impl ProjectileBundle {
+    pub fn new(
+        pos: Vec2,
+        vel: Vec2,
+        radius: f32,
+        damage: u32,
+        lifetime: f32,
+        room_state: &RoomState,
+    ) -> Self {
+        let shape = Shape::Circle {
+            center: Vec2::ZERO,
+            radius,
+        };
+        Self {
+            name: Name::new("projectile"),
+            projectile: Projectile { spent: false },
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::ONE * radius * 2.0),
+                    color: tailwind::AMBER_200.into(),
+                    ..default()
+                },
+                transform: Transform::from_xyz(pos.x, pos.y, ZIX_SHIP - 1.0)
+                    .with_rotation(Quat::from_rotation_z(vel.to_angle())),
+                ..default()
+            },
+            render_layers: SpriteLayer::render_layers(),
+            dyno_tran: DynoTran { vel },
+            // Wrapped, so bullets can hit things on the other side of the room edge
+            static_rx: StaticRx::from_kind_n_wrapped_shapes(
+                StaticRxKind::Stop,
+                vec![shape.clone()],
+                room_state,
+            ),
+            trigger_tx: TriggerTx::from_kind_n_wrapped_shapes(
+                TriggerKind::Bullet,
+                vec![shape],
+                room_state,
+            ),
+            damage: Damage::new(damage, vec![TriggerKind::Enemy]).with_knockback(30.0),
+            wrap: RoomWrap,
+            dying: Dying::new(lifetime),
+        }
+    }
+}
+
+/// A little burst where a projectile hit something
+fn spawn_impact(pos: Vec2, pending: &mut PendingParticles, rng: &mut impl Rng) {
+    for _ in 0..6 {
+        let vel = Vec2::from_angle(rng.gen_range(0.0..(2.0 * PI))) * rng.gen_range(20.0..60.0);
+        pending.push(
+            Particle::new(pos)
+                .with_vel(vel)
+                .with_colors(tailwind::AMBER_200.into(), tailwind::RED_600.into())
+                .with_sizes(2.0, 0.5)
+                .with_lifespan(0.3),
+        );
+    }
+}
+
+/// Projectiles die on the first thing they hit. The hurting part happens in `health.rs`, off the same records.
+fn resolve_projectile_hits(
+    mut projectiles: Query<(
+        Entity,
+        &mut Projectile,
+        &mut DynoTran,
+        &GlobalTransform,
+        &StaticRx,
+        &TriggerTx,
+    )>,
+    static_records: Query<&StaticCollisionRecord>,
+    trigger_records: Query<&TriggerCollisionRecord>,
+    targets: Query<(), Without<Dying>>,
+    mut pending: ResMut<PendingParticles>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    for (eid, mut projectile, mut dyno_tran, gtran, static_rx, trigger_tx) in &mut projectiles {
+        if projectile.spent {
+            continue;
+        }
+        let hit_static = static_rx
+            .collisions
+            .iter()
+            .any(|record_eid| static_records.contains(*record_eid));
+        let hit_enemy = trigger_tx.collisions.iter().any(|record_eid| {
+            trigger_records.get(*record_eid).is_ok_and(|record| {
+                record.rx_kind == TriggerKind::Enemy && targets.contains(record.rx_eid)
+            })
+        });
+        if !hit_static && !hit_enemy {
+            continue;
+        }
+        projectile.spent = true;
+        dyno_tran.vel = Vec2::ZERO;
+        spawn_impact(
+            gtran.translation().truncate(),
+            &mut pending,
+            rng.stream(RngStream::Particles),
+        );
+        // No more trigger, so it can't hurt anything else on the way out
+        commands.entity(eid).remove::<TriggerTx>().insert((
+            Dying::new(0.0).with_cause(DeathCause::Suicide),
+            Visibility::Hidden,
+        ));
+    }
+}
+
+pub(super) fn register_projectiles(app: &mut App) {
+    app.register_type::<Projectile>();
+
+    app.add_systems(
+        Update,
+        resolve_projectile_hits
+            .after(PhysicsSet)
+            .after(HealthSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
+}
@@ -0,0 +1,188 @@
+//! Shows where the ship will go while a launch is being aimed (left drag).
+//! The path comes from `predict_static_path`, so it bounces, sticks and wraps just like the real launch would.
+//! It's drawn as a line of dots evenly spaced in time (so they spread out where it's fast) with a bigger
+//! dot where the ship first hits something.
+
+use crate::prelude::*;
+
+use super::launch::LaunchConstants;
+
+#[derive(Resource, Reflect)]
+struct TrajectoryConstants {
+    /// How far ahead (in-game seconds) to predict
+    max_time: f32,
+    /// Only every this many frames of flight gets a dot
+    dot_every: usize,
+    max_dots: usize,
+    dot_size: f32,
+    contact_size: f32,
+    dot_color: Color,
+    contact_color: Color,
+}
+impl Default for TrajectoryConstants {
+    fn default() -> Self {
+        Self {
+            max_time: 1.5,
+            dot_every: 2,
+            max_dots: 32,
+            dot_size: 2.0,
+            contact_size: 5.0,
+            dot_color: tailwind::GRAY_300.with_alpha(0.7).into(),
+            contact_color: tailwind::AMBER_300.into(),
+        }
+    }
+}
+
+/// Holds the dots for one ship. Lives next to the ship (same parent), so it's cleaned up with it.
+#[derive(Component, Debug, Clone, Reflect)]
+struct TrajectoryPreview {
+    ship: Entity,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct TrajectoryDot(usize);
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct TrajectoryContact;
+
+fn dot_sprite(size: f32, color: Color) -> SpriteBundle {
+    SpriteBundle {
+        sprite: Sprite {
+            custom_size: Some(Vec2::ONE * size),
+            color,
+            ..default()
+        },
+        visibility: Visibility::Hidden,
+        ..default()
+    }
+}
+
+fn attach_trajectory_previews(
+    new_ships: Query<(Entity, Option<&Parent>), Added<Ship>>,
+    constants: Res<TrajectoryConstants>,
+    mut commands: Commands,
+) {
+    for (ship_eid, parent) in &new_ships {
+        let mut preview_commands = commands.spawn((
+            Name::new("trajectory_preview"),
+            TrajectoryPreview { ship: ship_eid },
+            spat_tran!(0.0, 0.0, ZIX_SHIP - 2.0),
+        ));
+        if let Some(parent) = parent {
+            preview_commands.set_parent(parent.get());
+        }
+        preview_commands.with_children(|preview| {
+            for ix in 0..constants.max_dots {
+                preview.spawn((
+                    Name::new(format!("trajectory_dot_{ix}")),
+                    TrajectoryDot(ix),
+                    dot_sprite(constants.dot_size, constants.dot_color),
+                    SpriteLayer::render_layers(),
+                ));
+            }
+            preview.spawn((
+                Name::new("trajectory_contact"),
+                TrajectoryContact,
+                dot_sprite(constants.contact_size, constants.contact_color),
+                SpriteLayer::render_layers(),
+            ));
+        });
+    }
+}
+
+fn update_trajectory_previews(
+    previews: Query<(Entity, &TrajectoryPreview, &GlobalTransform)>,
+    ships: Query<
+        (&GlobalTransform, &StaticRx, &LaunchCharges, Has<RoomWrap>),
+        (With<Ship>, Without<Dying>),
+    >,
+    providers: Query<(&StaticTx, &GlobalTransform), Without<PhysicsSuppressed>>,
+    mut dots: Query<(&TrajectoryDot, &Parent, &mut Transform, &mut Visibility)>,
+    mut contacts: Query<
+        (&Parent, &mut Transform, &mut Visibility),
+        (With<TrajectoryContact>, Without<TrajectoryDot>),
+    >,
+    drag_input: Res<DragInput>,
+    launch_constants: Res<LaunchConstants>,
+    stats: Res<ShipStats>,
+    meta_state: Res<State<MetaState>>,
+    constants: Res<TrajectoryConstants>,
+    mut commands: Commands,
+) {
+    let room_state = meta_state.get_room_state();
+    for (preview_eid, preview, preview_gtran) in &previews {
+        if commands.get_entity(preview.ship).is_none() {
+            commands.entity(preview_eid).despawn_recursive();
+            continue;
+        }
+        let path = ships
+            .get(preview.ship)
+            .ok()
+            .filter(|(_, _, charges, _)| charges.can_launch())
+            .and_then(|(ship_gtran, rx, _, wraps)| {
+                let drag_start = drag_input.get_left_drag_start()?;
+                let vel =
+                    launch_constants.launch_vel(drag_start - drag_input.get_world_pos(), &stats)?;
+                Some(predict_static_path(
+                    rx,
+                    ship_gtran.translation().truncate(),
+                    vel,
+                    providers
+                        .iter()
+                        .map(|(tx, tx_gtran)| (tx, tx_gtran.pos_n_angle())),
+                    room_state.as_ref().filter(|_| wraps),
+                    constants.max_time,
+                ))
+            });
+        let offset = preview_gtran.translation().truncate();
+        let dot_points: Vec<Vec2> = path
+            .as_ref()
+            .map(|path| {
+                path.points
+                    .iter()
+                    .skip(1)
+                    .step_by(constants.dot_every.max(1))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (dot, parent, mut tran, mut visibility) in &mut dots {
+            if parent.get() != preview_eid {
+                continue;
+            }
+            match dot_points.get(dot.0) {
+                Some(point) => {
+                    tran.translation = (*point - offset).extend(0.0);
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+        let contact = path.as_ref().and_then(|path| path.contact);
+        for (parent, mut tran, mut visibility) in &mut contacts {
+            if parent.get() != preview_eid {
+                continue;
+            }
+            match contact {
+                Some(point) => {
+                    tran.translation = (point - offset).extend(1.0);
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
+
+pub(super) fn register_trajectory(app: &mut App) {
+    app.register_type::<TrajectoryPreview>();
+    app.insert_resource(TrajectoryConstants::default());
+    debug_resource!(app, TrajectoryConstants);
+
+    app.add_systems(
+        Update,
+        (attach_trajectory_previews, update_trajectory_previews)
+            .chain()
+            .after(PhysicsSet),
+    );
+}
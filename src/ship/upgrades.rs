@@ -0,0 +1,229 @@
+//! Upgrades picked up between rooms. They're defined in `assets/upgrades/ship.upgrades.ron`, and each one is
+//! a list of modifiers to the ship's stats. The ids of the ones taken are saved with the run (see `SavedRun`),
+//! and `ShipStats` is rebuilt from them whenever they change.
+//!
+//! Ship systems read `ShipStats` instead of hardcoding these numbers. For every stat, all the `Add`s are
+//! applied to the base value first, then all the `Mult`s. An upgrade can also swap the ship's weapon
+//! (the most recently taken one wins).
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+
+use crate::prelude::*;
+
+const UPGRADES_FILE: &str = "upgrades/ship.upgrades.ron";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum ShipStat {
+    /// Multiplies launch speed
+    LaunchPower,
+    /// Multiplies how long bullet time can last
+    BulletTimeDuration,
+    /// Multiplies shots per second
+    FireRate,
+    /// Extra hit points (negative takes some away, but the ship always keeps at least one)
+    Hull,
+    /// The ship sticks to normal planets if it hits them slower than this (see `StaticRx::grip`)
+    Stickiness,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Reflect)]
+pub enum StatChange {
+    Add(f32),
+    Mult(f32),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+pub struct StatModifier {
+    pub stat: ShipStat,
+    pub change: StatChange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+pub struct UpgradeDef {
+    /// What gets saved, so don't change it once it's shipped
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub modifiers: Vec<StatModifier>,
+    /// Swaps the ship's weapon for this one
+    #[serde(default)]
+    pub weapon: Option<WeaponKind>,
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct UpgradePoolDef {
+    pub upgrades: Vec<UpgradeDef>,
+}
+impl UpgradePoolDef {
+    pub fn get(&self, id: &str) -> Option<&UpgradeDef> {
+        self.upgrades.iter().find(|upgrade| upgrade.id == id)
+    }
+}
+
+#[derive(Debug)]
+pub enum UpgradePoolDefLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+impl std::fmt::Display for UpgradePoolDefLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read upgrades file: {err}"),
+            Self::Ron(err) => write!(f, "couldn't parse upgrades file: {err}"),
+        }
+    }
+}
+impl std::error::Error for UpgradePoolDefLoaderError {}
+impl From<std::io::Error> for UpgradePoolDefLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for UpgradePoolDefLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+#[derive(Default)]
+struct UpgradePoolDefLoader;
+impl AssetLoader for UpgradePoolDefLoader {
+    type Asset = UpgradePoolDef;
+    type Settings = ();
+    type Error = UpgradePoolDefLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes::<UpgradePoolDef>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["upgrades.ron"]
+    }
+}
+
+/// Holds on to every upgrade that can be offered
+#[derive(Resource, Debug, Clone, Default)]
+pub struct UpgradePool {
+    handle: Handle<UpgradePoolDef>,
+}
+impl UpgradePool {
+    pub fn get_handle(&self) -> &Handle<UpgradePoolDef> {
+        &self.handle
+    }
+}
+
+/// The ship's stats after upgrades
+#[derive(Resource, Debug, Clone, Reflect)]
+pub struct ShipStats {
+    pub launch_power: f32,
+    pub bullet_time_duration: f32,
+    pub fire_rate: f32,
+    pub hull: f32,
+    pub stickiness: f32,
+    pub weapon: WeaponKind,
+}
+impl Default for ShipStats {
+    fn default() -> Self {
+        Self {
+            launch_power: 1.0,
+            bullet_time_duration: 1.0,
+            fire_rate: 1.0,
+            hull: 0.0,
+            stickiness: 0.0,
+            weapon: default(),
+        }
+    }
+}
+impl ShipStats {
+    fn stat_mut(&mut self, stat: ShipStat) -> &mut f32 {
+        match stat {
+            ShipStat::LaunchPower => &mut self.launch_power,
+            ShipStat::BulletTimeDuration => &mut self.bullet_time_duration,
+            ShipStat::FireRate => &mut self.fire_rate,
+            ShipStat::Hull => &mut self.hull,
+            ShipStat::Stickiness => &mut self.stickiness,
+        }
+    }
+
+    /// Base stats with every one of these upgrades applied
+    pub fn from_upgrades(upgrades: &[&UpgradeDef]) -> Self {
+        let mut stats = Self::default();
+        let modifiers = || upgrades.iter().flat_map(|upgrade| upgrade.modifiers.iter());
+        for modifier in modifiers() {
+            if let StatChange::Add(amount) = modifier.change {
+                *stats.stat_mut(modifier.stat) += amount;
+            }
+        }
+        for modifier in modifiers() {
+            if let StatChange::Mult(amount) = modifier.change {
+                *stats.stat_mut(modifier.stat) *= amount;
+            }
+        }
+        if let Some(weapon) = upgrades.iter().rev().find_map(|upgrade| upgrade.weapon) {
+            stats.weapon = weapon;
+        }
+        stats
+    }
+
+    /// Extra hit points (whole ones only). Negative means fewer.
+    pub fn extra_hp(&self) -> i32 {
+        self.hull.floor() as i32
+    }
+}
+
+fn load_upgrade_pool(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(UpgradePool {
+        handle: asset_server.load(UPGRADES_FILE),
+    });
+}
+
+/// Keeps `ShipStats` in line with the upgrades taken this run
+fn update_ship_stats(
+    saved_run: Res<SavedRun>,
+    pool: Res<UpgradePool>,
+    pool_defs: Res<Assets<UpgradePoolDef>>,
+    mut asset_events: EventReader<AssetEvent<UpgradePoolDef>>,
+    mut stats: ResMut<ShipStats>,
+) {
+    let pool_changed = asset_events.read().count() > 0;
+    if !saved_run.is_changed() && !pool_changed {
+        return;
+    }
+    let Some(pool_def) = pool_defs.get(&pool.handle) else {
+        return;
+    };
+    let upgrades: Vec<&UpgradeDef> = saved_run
+        .get_upgrades()
+        .iter()
+        .filter_map(|id| pool_def.get(id))
+        .collect();
+    *stats = ShipStats::from_upgrades(&upgrades);
+}
+
+/// Sticking to normal planets is just the ship's static rx being grippy
+fn apply_stickiness(stats: Res<ShipStats>, mut ships: Query<&mut StaticRx, With<Ship>>) {
+    for mut rx in &mut ships {
+        if rx.grip != stats.stickiness {
+            rx.grip = stats.stickiness;
+        }
+    }
+}
+
+pub(super) fn register_upgrades(app: &mut App) {
+    app.init_asset::<UpgradePoolDef>();
+    app.init_asset_loader::<UpgradePoolDefLoader>();
+    app.insert_resource(UpgradePool::default());
+    app.insert_resource(ShipStats::default());
+    debug_resource!(app, ShipStats);
+
+    app.add_systems(Startup, load_upgrade_pool);
+    app.add_systems(Update, (update_ship_stats, apply_stickiness).chain());
+}
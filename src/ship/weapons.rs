@@ -0,0 +1,291 @@
+//! Turns `Fire` input into projectiles (see `projectile.rs`). The ship has one weapon at a time, picked
+//! by upgrades (see `ShipStats::weapon`):
+//! - `Single`: one bullet, straight where you aimed
+//! - `Spread`: a fan of bullets, runs hot
+//! - `Charge`: hold the right drag to power up one big bullet
+//!
+//! Every shot uses ammo, which (like launch charges) only comes back while the ship is stuck to something.
+//! Every shot also adds heat, which cools off over time. Hitting full heat overheats the weapon, and it can't
+//! fire again until it's cooled all the way down. The cooldown between shots is scaled by the `fire_rate` stat.
+
+use crate::prelude::*;
+
+#[derive(Resource, Reflect)]
+struct WeaponConstants {
+    /// Aim drags shorter than this are ignored (they were probably just clicks)
+    min_aim: f32,
+    bullet_speed: f32,
+    bullet_radius: f32,
+    bullet_lifetime: f32,
+    /// How far in front of the ship bullets spawn
+    muzzle_offset: f32,
+    max_ammo: u32,
+    /// How long the ship has to be stuck to get a bullet back
+    reload_time: f32,
+    /// Time between shots (before upgrades)
+    cooldown: f32,
+    /// Heat lost per second. Heat goes from 0 to 1, and hitting 1 overheats.
+    cool_rate: f32,
+    single_heat: f32,
+    spread_heat: f32,
+    spread_count: u32,
+    /// Angle between the outermost bullets of a spread
+    spread_angle: f32,
+    /// How long it takes to fully charge a charge shot
+    charge_time: f32,
+    charge_min_heat: f32,
+    charge_max_heat: f32,
+    charge_max_damage: u32,
+    charge_max_radius: f32,
+}
+impl Default for WeaponConstants {
+    fn default() -> Self {
+        Self {
+            min_aim: 4.0,
+            bullet_speed: 300.0,
+            bullet_radius: 1.5,
+            bullet_lifetime: 1.2,
+            muzzle_offset: 8.0,
+            max_ammo: 12,
+            reload_time: 0.15,
+            cooldown: 0.15,
+            cool_rate: 0.4,
+            single_heat: 0.12,
+            spread_heat: 0.3,
+            spread_count: 5,
+            spread_angle: PI / 4.0,
+            charge_time: 1.0,
+            charge_min_heat: 0.15,
+            charge_max_heat: 0.6,
+            charge_max_damage: 4,
+            charge_max_radius: 4.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum WeaponKind {
+    #[default]
+    Single,
+    Spread,
+    Charge,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+pub struct ShipWeapon {
+    kind: WeaponKind,
+    ammo: u32,
+    max_ammo: u32,
+    /// Time spent stuck towards the next bullet
+    reload: f32,
+    heat: f32,
+    overheated: bool,
+    cooldown: f32,
+    /// How charged up a charge shot is, 0 to 1
+    charge: f32,
+}
+impl ShipWeapon {
+    pub fn new(kind: WeaponKind, max_ammo: u32) -> Self {
+        Self {
+            kind,
+            ammo: max_ammo,
+            max_ammo,
+            reload: 0.0,
+            heat: 0.0,
+            overheated: false,
+            cooldown: 0.0,
+            charge: 0.0,
+        }
+    }
+
+    impl_get_set_with!(kind, WeaponKind);
+    impl_get!(ammo, u32);
+    impl_get!(max_ammo, u32);
+    impl_get!(heat, f32);
+    impl_get!(overheated, bool);
+    impl_get!(charge, f32);
+
+    pub fn can_fire(&self) -> bool {
+        self.ammo > 0 && !self.overheated && self.cooldown <= 0.0
+    }
+}
+
+/// Ships get a weapon when they show up, so every ship bundle starts with a full clip
+fn attach_ship_weapons(
+    new_ships: Query<Entity, (Added<Ship>, Without<ShipWeapon>)>,
+    constants: Res<WeaponConstants>,
+    stats: Res<ShipStats>,
+    mut commands: Commands,
+) {
+    for eid in &new_ships {
+        commands
+            .entity(eid)
+            .insert(ShipWeapon::new(stats.weapon, constants.max_ammo));
+    }
+}
+
+/// Weapon upgrades taken mid-room apply right away
+fn upgrade_ship_weapons(stats: Res<ShipStats>, mut weapons: Query<&mut ShipWeapon>) {
+    if !stats.is_changed() {
+        return;
+    }
+    for mut weapon in &mut weapons {
+        if weapon.kind != stats.weapon {
+            weapon.kind = stats.weapon;
+            weapon.charge = 0.0;
+        }
+    }
+}
+
+fn spawn_muzzle_flash(pos: Vec2, dir: Vec2, pending: &mut PendingParticles, rng: &mut impl Rng) {
+    for _ in 0..4 {
+        let angle = dir.to_angle() + rng.gen_range(-0.4..0.4);
+        pending.push(
+            Particle::new(pos)
+                .with_vel(Vec2::from_angle(angle) * rng.gen_range(30.0..80.0))
+                .with_colors(Color::WHITE, tailwind::AMBER_400.into())
+                .with_sizes(2.5, 0.5)
+                .with_lifespan(0.15),
+        );
+    }
+}
+
+fn fire_weapons(
+    mut fires: EventReader<Fire>,
+    mut ships: Query<
+        (&mut ShipWeapon, &GlobalTransform, Option<&Parent>),
+        (With<Ship>, Without<Dying>),
+    >,
+    constants: Res<WeaponConstants>,
+    stats: Res<ShipStats>,
+    meta_state: Res<State<MetaState>>,
+    mut pending: ResMut<PendingParticles>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let Some(room_state) = meta_state.play_room_state() else {
+        fires.clear();
+        return;
+    };
+    for fire in fires.read() {
+        if fire.0.length() < constants.min_aim {
+            continue;
+        }
+        let dir = fire.0.normalize();
+        for (mut weapon, gtran, parent) in &mut ships {
+            if !weapon.can_fire() {
+                continue;
+            }
+            let pos = gtran.translation().truncate() + dir * constants.muzzle_offset;
+            // (direction, radius, damage) of each bullet in this shot
+            let (bullets, heat) = match weapon.kind {
+                WeaponKind::Single => (
+                    vec![(dir, constants.bullet_radius, 1)],
+                    constants.single_heat,
+                ),
+                WeaponKind::Spread => {
+                    let count = constants.spread_count.max(1);
+                    let (first, step) = if count > 1 {
+                        (
+                            dir.to_angle() - constants.spread_angle / 2.0,
+                            constants.spread_angle / (count - 1) as f32,
+                        )
+                    } else {
+                        (dir.to_angle(), 0.0)
+                    };
+                    let bullets = (0..count)
+                        .map(|ix| {
+                            let angle = first + step * ix as f32;
+                            (Vec2::from_angle(angle), constants.bullet_radius, 1)
+                        })
+                        .collect();
+                    (bullets, constants.spread_heat)
+                }
+                WeaponKind::Charge => {
+                    let charge = weapon.charge;
+                    let radius = constants.bullet_radius
+                        + (constants.charge_max_radius - constants.bullet_radius) * charge;
+                    let damage = 1
+                        + ((constants.charge_max_damage.max(1) - 1) as f32 * charge).round() as u32;
+                    let heat = constants.charge_min_heat
+                        + (constants.charge_max_heat - constants.charge_min_heat) * charge;
+                    (vec![(dir, radius, damage)], heat)
+                }
+            };
+            for (bullet_dir, radius, damage) in bullets {
+                let mut bullet_commands = commands.spawn(ProjectileBundle::new(
+                    pos,
+                    bullet_dir * constants.bullet_speed,
+                    radius,
+                    damage,
+                    constants.bullet_lifetime,
+                    &room_state,
+                ));
+                if let Some(parent) = parent {
+                    bullet_commands.set_parent(parent.get());
+                }
+            }
+            spawn_muzzle_flash(pos, dir, &mut pending, rng.stream(RngStream::Particles));
+            weapon.ammo -= 1;
+            weapon.cooldown = constants.cooldown / stats.fire_rate.max(0.01);
+            weapon.heat += heat;
+            if weapon.heat >= 1.0 {
+                weapon.heat = 1.0;
+                weapon.overheated = true;
+            }
+            weapon.charge = 0.0;
+        }
+    }
+}
+
+/// Cools off, reloads while stuck, and builds up charge while aiming
+fn update_ship_weapons(
+    mut ships: Query<(&mut ShipWeapon, Option<&Stuck>)>,
+    constants: Res<WeaponConstants>,
+    drag_input: Res<DragInput>,
+    bullet_time: Res<BulletTime>,
+) {
+    let dt = bullet_time.delta_seconds();
+    let aiming = drag_input.get_right_drag_start().is_some();
+    for (mut weapon, stuck) in &mut ships {
+        weapon.cooldown = (weapon.cooldown - dt).max(0.0);
+        weapon.heat = (weapon.heat - constants.cool_rate * dt).max(0.0);
+        if weapon.heat <= 0.0 {
+            weapon.overheated = false;
+        }
+        weapon.charge = if aiming && weapon.kind == WeaponKind::Charge {
+            (weapon.charge + dt / constants.charge_time).min(1.0)
+        } else {
+            0.0
+        };
+        if stuck.is_none() || weapon.ammo >= weapon.max_ammo {
+            weapon.reload = 0.0;
+            continue;
+        }
+        weapon.reload += dt;
+        if weapon.reload >= constants.reload_time {
+            weapon.reload = 0.0;
+            weapon.ammo += 1;
+        }
+    }
+}
+
+pub(super) fn register_weapons(app: &mut App) {
+    app.register_type::<ShipWeapon>();
+    app.insert_resource(WeaponConstants::default());
+    debug_resource!(app, WeaponConstants);
+
+    app.add_systems(
+        Update,
+        (
+            attach_ship_weapons,
+            upgrade_ship_weapons,
+            fire_weapons,
+            update_ship_weapons,
+        )
+            .chain()
+            .after(InputSet)
+            .before(PhysicsSet)
+            .run_if(in_state(PhysicsState::Active)),
+    );
+}
@@ -1,8 +1,12 @@
 use crate::prelude::*;
 
+pub mod progression;
 pub mod room;
+pub mod transition;
 
+pub use progression::*;
 pub use room::*;
+pub use transition::*;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, States)]
 pub enum AppMode {
@@ -10,17 +14,22 @@ pub enum AppMode {
     Prod,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
 pub enum MenuState {
     Studio,
     Title,
+    Settings,
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect)]
-pub enum CutsceneState {}
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+pub enum CutsceneState {
+    Intro,
+}
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect)]
-pub enum TutorialState {}
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
+pub enum TutorialState {
+    Basics,
+}
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, States, Reflect)]
 pub enum MetaState {
@@ -37,6 +46,18 @@ impl MetaState {
             .unwrap_or(IDEAL_VEC)
             .as_vec2()
     }
+
+    /// The room that gameplay is happening in. Tutorials don't have a `RoomState`, so they get an
+    /// ideal-sized stand-in (same as `wrap_size`).
+    pub fn play_room_state(&self) -> Option<RoomState> {
+        match self {
+            MetaState::Room(room_state) => Some(room_state.clone()),
+            MetaState::Tutorial(_) => {
+                Some(RoomState::xth_encounter(EncounterKind::SimpOnly, 0).with_room_size(IDEAL_VEC))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Kills some verbosity with reading meta states
@@ -195,10 +216,7 @@ impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         // Ground truth states
         app.insert_state(AppMode::Dev);
-        app.insert_state(MetaState::Room(RoomState::xth_encounter(
-            EncounterKind::SimpOnly,
-            1,
-        ))); // INITIAL STATE (control f this silly)
+        app.insert_state(MetaState::Menu(MenuState::Studio)); // INITIAL STATE (control f this silly)
         app.insert_state(MetaTransitionState::Stable);
         app.insert_state(PauseState::Unpaused);
         // Computed states
@@ -207,5 +225,9 @@ impl Plugin for StatePlugin {
         app.add_computed_state::<MetaStateKind>();
         // Overcrowded states
         room::register_room_states(app);
+        // Progression
+        progression::register_progression(app);
+        // Transitions
+        transition::register_transition(app);
     }
 }
@@ -0,0 +1,281 @@
+//! The order rooms come in during a run. It's a graph defined in `assets/progression/main.progression.ron`.
+//! Each node is an encounter (kind + difficulty) played in a room template, and lists the nodes you can go to
+//! after clearing it. More than one means the player gets to pick.
+//!
+//! The node being played is kept in `CurrentNode` (and saved with the run), so nodes can share an encounter.
+//! Use `Progression::next_options` to find out where you can go from a node.
+
+use std::collections::BTreeMap;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState},
+    ecs::system::SystemParam,
+};
+
+use crate::prelude::*;
+
+const PROGRESSION_FILE: &str = "progression/main.progression.ron";
+
+/// Where the geometry for a node's room comes from
+#[derive(Debug, Clone, Serialize, Deserialize, Reflect)]
+pub enum RoomTemplate {
+    /// A hand-authored room file, i.e. "rooms/debug.room.ron"
+    File(String),
+    /// A fresh room from `generate_room`
+    Generated,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressionNode {
+    /// What the player sees when picking where to go
+    pub label: String,
+    pub room: RoomTemplate,
+    pub encounter: EncounterDef,
+    #[serde(default)]
+    pub next: Vec<String>,
+}
+
+#[derive(Asset, TypePath, Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressionDef {
+    /// Where new runs begin
+    pub start: String,
+    pub nodes: BTreeMap<String, ProgressionNode>,
+}
+impl ProgressionDef {
+    /// The first node with this encounter. Only for saves from before the node id was saved, since
+    /// nodes can share an encounter.
+    fn guess_node(&self, room_state: &RoomState) -> Option<(&str, &ProgressionNode)> {
+        let kind = room_state.encounter_state.kind;
+        let difficulty = room_state.encounter_state.difficulty;
+        self.nodes
+            .iter()
+            .find(|(_, node)| {
+                node.encounter.kind == kind && node.encounter.difficulty == difficulty
+            })
+            .map(|(id, node)| (id.as_str(), node))
+    }
+
+    /// Makes sure every id points somewhere
+    fn validate(&self) -> Result<(), String> {
+        if !self.nodes.contains_key(&self.start) {
+            return Err(format!("start node '{}' doesn't exist", self.start));
+        }
+        for (id, node) in &self.nodes {
+            for next in &node.next {
+                if !self.nodes.contains_key(next) {
+                    return Err(format!("node '{id}' goes to '{next}', which doesn't exist"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A room the player could go to next
+#[derive(Debug, Clone, Reflect)]
+pub struct ProgressionOption {
+    pub node: String,
+    pub label: String,
+    pub room: RoomTemplate,
+    pub room_state: RoomState,
+}
+impl ProgressionOption {
+    fn from_node(id: &str, node: &ProgressionNode) -> Self {
+        Self {
+            node: id.to_string(),
+            label: node.label.clone(),
+            room: node.room.clone(),
+            room_state: RoomState::xth_encounter(node.encounter.kind, node.encounter.difficulty),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ProgressionDefLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Invalid(String),
+}
+impl std::fmt::Display for ProgressionDefLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read progression file: {err}"),
+            Self::Ron(err) => write!(f, "couldn't parse progression file: {err}"),
+            Self::Invalid(reason) => write!(f, "invalid progression file: {reason}"),
+        }
+    }
+}
+impl std::error::Error for ProgressionDefLoaderError {}
+impl From<std::io::Error> for ProgressionDefLoaderError {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+impl From<ron::error::SpannedError> for ProgressionDefLoaderError {
+    fn from(value: ron::error::SpannedError) -> Self {
+        Self::Ron(value)
+    }
+}
+
+#[derive(Default)]
+struct ProgressionDefLoader;
+impl AssetLoader for ProgressionDefLoader {
+    type Asset = ProgressionDef;
+    type Settings = ();
+    type Error = ProgressionDefLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        let def = ron::de::from_bytes::<ProgressionDef>(&bytes)?;
+        def.validate().map_err(ProgressionDefLoaderError::Invalid)?;
+        Ok(def)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["progression.ron"]
+    }
+}
+
+/// Holds on to the progression graph so it stays loaded
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ProgressionGraph {
+    handle: Handle<ProgressionDef>,
+}
+
+/// The progression node being played, and the seed its room was generated from (if it was generated).
+/// Saved with the run, so continuing puts you back in the same node and the same room.
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+pub struct CurrentNode {
+    id: Option<String>,
+    room_seed: Option<u64>,
+}
+impl CurrentNode {
+    pub fn get_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn get_room_seed(&self) -> Option<u64> {
+        self.room_seed
+    }
+}
+
+/// Everything needed to figure out (and go to) the next room
+#[derive(SystemParam)]
+pub struct Progression<'w> {
+    graph: Res<'w, ProgressionGraph>,
+    defs: Res<'w, Assets<ProgressionDef>>,
+    asset_server: Res<'w, AssetServer>,
+}
+impl Progression<'_> {
+    /// `None` until the progression file is loaded
+    pub fn get_def(&self) -> Option<&ProgressionDef> {
+        self.defs.get(&self.graph.handle)
+    }
+
+    /// True until the progression file is either loaded or has failed to load
+    pub fn is_loading(&self) -> bool {
+        matches!(
+            self.asset_server.load_state(self.graph.handle.id()),
+            LoadState::NotLoaded | LoadState::Loading
+        )
+    }
+
+    /// The first room of a new run
+    pub fn start_option(&self) -> Option<ProgressionOption> {
+        let def = self.get_def()?;
+        let node = def.nodes.get(&def.start)?;
+        Some(ProgressionOption::from_node(&def.start, node))
+    }
+
+    /// The option for a node (i.e. to pick a run back up where it left off)
+    pub fn option_for(&self, id: &str) -> Option<ProgressionOption> {
+        let node = self.get_def()?.nodes.get(id)?;
+        Some(ProgressionOption::from_node(id, node))
+    }
+
+    /// The option for a saved run. Old saves don't know their node, so that's guessed from the encounter.
+    pub fn saved_option(&self, run: &RunSave) -> Option<ProgressionOption> {
+        match &run.node {
+            Some(id) => self.option_for(id),
+            None => {
+                let (id, node) = self.get_def()?.guess_node(&run.room_state())?;
+                Some(ProgressionOption::from_node(id, node))
+            }
+        }
+    }
+
+    /// Where the player can go after clearing this node. Empty if it's the end of the graph
+    /// (or the node isn't in it).
+    pub fn next_options(&self, id: &str) -> Vec<ProgressionOption> {
+        let Some(def) = self.get_def() else {
+            return vec![];
+        };
+        let Some(node) = def.nodes.get(id) else {
+            return vec![];
+        };
+        node.next
+            .iter()
+            .filter_map(|id| {
+                def.nodes
+                    .get(id)
+                    .map(|next| ProgressionOption::from_node(id, next))
+            })
+            .collect()
+    }
+}
+
+/// Makes the room file for an option active, and makes its node the `CurrentNode`.
+/// Generated rooms are added as assets, so they spawn just like files. They use `room_seed` if there is one
+/// (i.e. to rebuild a saved room), otherwise a fresh seed.
+pub fn activate_room_template(
+    option: &ProgressionOption,
+    room_seed: Option<u64>,
+    asset_server: &AssetServer,
+    room_defs: &mut Assets<RoomDef>,
+    gen_constants: &RoomGenConstants,
+    rng: &mut GameRng,
+    commands: &mut Commands,
+) {
+    let (handle, room_seed) = match &option.room {
+        RoomTemplate::File(path) => (asset_server.load(path.clone()), None),
+        RoomTemplate::Generated => {
+            let seed = room_seed.unwrap_or_else(|| rng.stream(RngStream::Environment).gen());
+            let params = RoomGenParams {
+                seed,
+                kind: option.room_state.encounter_state.kind,
+                difficulty: option.room_state.encounter_state.difficulty,
+            };
+            (
+                room_defs.add(generate_room(params, gen_constants)),
+                Some(seed),
+            )
+        }
+    };
+    commands.insert_resource(ActiveRoomFile::new(handle));
+    commands.insert_resource(CurrentNode {
+        id: Some(option.node.clone()),
+        room_seed,
+    });
+}
+
+fn load_progression(asset_server: Res<AssetServer>, mut commands: Commands) {
+    commands.insert_resource(ProgressionGraph {
+        handle: asset_server.load(PROGRESSION_FILE),
+    });
+}
+
+pub(super) fn register_progression(app: &mut App) {
+    app.init_asset::<ProgressionDef>();
+    app.init_asset_loader::<ProgressionDefLoader>();
+    app.insert_resource(ProgressionGraph::default());
+    app.register_type::<CurrentNode>();
+    app.insert_resource(CurrentNode::default());
+
+    app.add_systems(Startup, load_progression);
+}
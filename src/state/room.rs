@@ -1,10 +1,14 @@
 use crate::prelude::*;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
 pub enum EncounterKind {
     SimpOnly,
     SpewOnly,
     Both,
+    /// One big fight
+    Boss,
+    /// No enemies, just a breather
+    Rest,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Reflect, Default)]
@@ -40,47 +44,16 @@ impl RoomState {
         }
     }
 
-    /// The next room to go to (assuming the bird doesn't die, or if it is dead, wants to play again)
-    pub fn next_room(&self) -> Self {
-        match (self.encounter_state.kind, self.encounter_state.difficulty) {
-            (EncounterKind::SimpOnly, d) => {
-                if d < 3 {
-                    RoomState {
-                        room_size: self.room_size,
-                        encounter_state: EncounterState {
-                            kind: EncounterKind::SimpOnly,
-                            difficulty: d + 1,
-                            progress: EncounterProgress::Entering,
-                        },
-                    }
-                } else {
-                    RoomState {
-                        room_size: self.room_size,
-                        encounter_state: EncounterState {
-                            kind: EncounterKind::SpewOnly,
-                            difficulty: 1,
-                            progress: EncounterProgress::Entering,
-                        },
-                    }
-                }
-            }
-            (EncounterKind::SpewOnly, _) => RoomState {
-                room_size: self.room_size,
-                encounter_state: EncounterState {
-                    kind: EncounterKind::Both,
-                    difficulty: 1,
-                    progress: EncounterProgress::Entering,
-                },
-            },
-            (EncounterKind::Both, d) => RoomState {
-                room_size: self.room_size,
-                encounter_state: EncounterState {
-                    kind: EncounterKind::Both,
-                    difficulty: d + 1,
-                    progress: EncounterProgress::Entering,
-                },
-            },
-        }
+    /// This same room, but a different size
+    pub fn with_room_size(mut self, room_size: UVec2) -> Self {
+        self.room_size = room_size;
+        self
+    }
+
+    /// This same room, but with a different encounter progress
+    pub fn with_progress(mut self, progress: EncounterProgress) -> Self {
+        self.encounter_state.progress = progress;
+        self
     }
 
     /// Gets the offsets to show/update mirages to be exactly one room away in 8 dirs
@@ -0,0 +1,238 @@
+//! Orchestrates moving between meta states.
+//! Gameplay sends a `RequestTransition`, and then this walks `MetaTransitionState` through:
+//! - Leaving: fade the screen out (old content is still around)
+//! - Waiting: old content is despawned and `MetaState` is `Transition` for a frame, then switches to the next state
+//!   (this is when `OnEnter` systems for the new state will spawn their content)
+//! - Entering: fade the screen back in
+//! - Stable: done
+//! A `TransitionEvent` is sent at the start of each phase.
+
+use crate::prelude::*;
+
+/// Send this to go to a new meta state. Ignored if we're already in the middle of a transition.
+#[derive(Event, Debug, Clone)]
+pub struct RequestTransition {
+    pub to: MetaState,
+}
+
+/// Sent at the start of each phase of a transition
+#[derive(Event, Debug, Clone)]
+pub enum TransitionEvent {
+    StartedLeaving { from: MetaState, to: MetaState },
+    StartedWaiting { from: MetaState, to: MetaState },
+    StartedEntering { to: MetaState },
+    Finished { to: MetaState },
+}
+
+#[derive(Resource, Reflect)]
+struct TransitionConstants {
+    leaving_time: f32,
+    entering_time: f32,
+    color: Color,
+}
+impl Default for TransitionConstants {
+    fn default() -> Self {
+        Self {
+            leaving_time: 0.4,
+            entering_time: 0.4,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// The thing that covers the screen during a transition
+#[derive(Component, Debug, Clone, Reflect)]
+struct TransitionCover {
+    time: f32,
+}
+
+#[derive(Bundle)]
+struct TransitionCoverBundle {
+    name: Name,
+    cover: TransitionCover,
+    sprite: SpriteBundle,
+    render_layers: RenderLayers,
+}
+impl TransitionCoverBundle {
+    fn new(color: Color) -> Self {
+        Self {
+            name: Name::new("transition_cover"),
+            cover: TransitionCover { time: 0.0 },
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(MENU_VEC_f32),
+                    color: color.with_alpha(0.0),
+                    ..default()
+                },
+                ..default()
+            },
+            render_layers: MenuLayer::render_layers(),
+        }
+    }
+}
+
+fn start_transition(
+    mut requests: EventReader<RequestTransition>,
+    meta_state: Res<State<MetaState>>,
+    transition_state: Res<State<MetaTransitionState>>,
+    mut next_transition_state: ResMut<NextState<MetaTransitionState>>,
+    mut events: EventWriter<TransitionEvent>,
+    constants: Res<TransitionConstants>,
+    troot: Res<TransitionRoot>,
+    mut commands: Commands,
+) {
+    // Only the most recent request matters
+    let Some(request) = requests.read().last() else {
+        return;
+    };
+    if !matches!(transition_state.get(), MetaTransitionState::Stable) {
+        return;
+    }
+    commands.entity(troot.eid()).despawn_descendants();
+    commands
+        .spawn(TransitionCoverBundle::new(constants.color))
+        .set_parent(troot.eid());
+    next_transition_state.set(MetaTransitionState::Leaving {
+        next_meta_state: request.to,
+    });
+    events.send(TransitionEvent::StartedLeaving {
+        from: *meta_state.get(),
+        to: request.to,
+    });
+}
+
+/// Gets rid of everything under the root for a given meta state
+fn despawn_content(
+    meta_state: &MetaState,
+    commands: &mut Commands,
+    roots: (&MenuRoot, &CutsceneRoot, &TutorialRoot, &RoomRoot),
+) {
+    let (menu_root, cutscene_root, tutorial_root, room_root) = roots;
+    let root_eid = match meta_state {
+        MetaState::Menu(_) => menu_root.eid(),
+        MetaState::Cutscene(_) => cutscene_root.eid(),
+        MetaState::Tutorial(_) => tutorial_root.eid(),
+        MetaState::Room(_) => room_root.eid(),
+        MetaState::Transition => return,
+    };
+    commands.entity(root_eid).despawn_descendants();
+}
+
+fn update_leaving(
+    meta_state: Res<State<MetaState>>,
+    transition_state: Res<State<MetaTransitionState>>,
+    mut next_transition_state: ResMut<NextState<MetaTransitionState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+    mut events: EventWriter<TransitionEvent>,
+    mut cover_q: Query<(&mut TransitionCover, &mut Sprite)>,
+    constants: Res<TransitionConstants>,
+    time: Res<Time>,
+    mut commands: Commands,
+    roots: (
+        Res<MenuRoot>,
+        Res<CutsceneRoot>,
+        Res<TutorialRoot>,
+        Res<RoomRoot>,
+    ),
+) {
+    let MetaTransitionState::Leaving {
+        next_meta_state: to,
+    } = *transition_state.get()
+    else {
+        return;
+    };
+    let mut done = true;
+    for (mut cover, mut sprite) in &mut cover_q {
+        cover.time += time.delta_seconds();
+        let frac = (cover.time / constants.leaving_time).min(1.0);
+        sprite.color.set_alpha(Spleen::EaseInQuad.interp(frac));
+        done = done && frac >= 1.0;
+    }
+    if !done {
+        return;
+    }
+    despawn_content(
+        meta_state.get(),
+        &mut commands,
+        (&*roots.0, &*roots.1, &*roots.2, &*roots.3),
+    );
+    next_meta_state.set(MetaState::Transition);
+    next_transition_state.set(MetaTransitionState::Waiting {
+        next_meta_state: to,
+    });
+    events.send(TransitionEvent::StartedWaiting {
+        from: *meta_state.get(),
+        to,
+    });
+}
+
+fn update_waiting(
+    transition_state: Res<State<MetaTransitionState>>,
+    mut next_transition_state: ResMut<NextState<MetaTransitionState>>,
+    mut next_meta_state: ResMut<NextState<MetaState>>,
+    mut events: EventWriter<TransitionEvent>,
+    mut cover_q: Query<&mut TransitionCover>,
+) {
+    let MetaTransitionState::Waiting {
+        next_meta_state: to,
+    } = *transition_state.get()
+    else {
+        return;
+    };
+    for mut cover in &mut cover_q {
+        cover.time = 0.0;
+    }
+    next_meta_state.set(to);
+    next_transition_state.set(MetaTransitionState::Entering);
+    events.send(TransitionEvent::StartedEntering { to });
+}
+
+fn update_entering(
+    meta_state: Res<State<MetaState>>,
+    mut next_transition_state: ResMut<NextState<MetaTransitionState>>,
+    mut events: EventWriter<TransitionEvent>,
+    mut cover_q: Query<(Entity, &mut TransitionCover, &mut Sprite)>,
+    constants: Res<TransitionConstants>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let mut done = true;
+    for (eid, mut cover, mut sprite) in &mut cover_q {
+        cover.time += time.delta_seconds();
+        let frac = (cover.time / constants.entering_time).min(1.0);
+        sprite
+            .color
+            .set_alpha(1.0 - Spleen::EaseOutQuad.interp(frac));
+        if frac >= 1.0 {
+            commands.entity(eid).despawn_recursive();
+        } else {
+            done = false;
+        }
+    }
+    if !done {
+        return;
+    }
+    next_transition_state.set(MetaTransitionState::Stable);
+    events.send(TransitionEvent::Finished {
+        to: *meta_state.get(),
+    });
+}
+
+pub(super) fn register_transition(app: &mut App) {
+    app.register_type::<TransitionCover>();
+    app.insert_resource(TransitionConstants::default());
+    debug_resource!(app, TransitionConstants);
+
+    app.add_event::<RequestTransition>();
+    app.add_event::<TransitionEvent>();
+
+    app.add_systems(
+        Update,
+        (
+            start_transition.run_if(in_state(TransitionState::Stable)),
+            update_leaving.run_if(in_state(TransitionState::Leaving)),
+            update_waiting.run_if(in_state(TransitionState::Waiting)),
+            update_entering.run_if(in_state(TransitionState::Entering)),
+        ),
+    );
+}
@@ -0,0 +1,258 @@
+//! Tutorials are a list of steps. Each step shows a prompt, (optionally) slows down time, and waits
+//! for the player to do something before moving on to the next one.
+//! Everything a tutorial spawns lives under `TutorialRoot`, so it's cleaned up by the transition out.
+
+use crate::prelude::*;
+
+pub mod scripts;
+
+pub use scripts::*;
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TutorialSet;
+
+/// What the player has to do to finish a step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialGoal {
+    /// Send the ship flying
+    Launch,
+    /// Land on a planet
+    Stick,
+    /// Shoot down a suicido (if it blows itself up, another one shows up)
+    KillSuicido,
+    /// Shoot at least once
+    Fire,
+    /// Have a conversation (see `convo`) and finish it
+    Talk(&'static str),
+}
+
+/// Things a step can add to the tutorial room when it starts
+#[derive(Debug, Clone)]
+pub enum TutorialSpawn {
+    Planet { pos: Vec2, radius: f32 },
+    Suicido { pos: Vec2 },
+}
+
+#[derive(Debug, Clone)]
+pub struct TutorialStep {
+    pub prompt: &'static str,
+    /// Slows down time for the whole step
+    pub bullet_time: bool,
+    pub spawns: Vec<TutorialSpawn>,
+    pub goal: TutorialGoal,
+}
+
+#[derive(Debug, Clone)]
+pub struct TutorialScript {
+    /// Where the ship starts
+    pub ship_pos: Vec2,
+    pub steps: Vec<TutorialStep>,
+    /// Where to go once the tutorial is over
+    pub then: MetaState,
+}
+
+#[derive(Component, Debug, Clone, Reflect)]
+struct TutorialPrompt;
+
+#[derive(Bundle)]
+struct TutorialPromptBundle {
+    name: Name,
+    prompt: TutorialPrompt,
+    text: Text2dBundle,
+    render_layers: RenderLayers,
+}
+impl TutorialPromptBundle {
+    fn new(prompt: &str) -> Self {
+        Self {
+            name: Name::new("tutorial_prompt"),
+            prompt: TutorialPrompt,
+            text: Text2dBundle {
+                text: Text::from_section(
+                    prompt,
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_xyz(0.0, MENU_VEC_f32.y * 0.38, ZIX_MENU),
+                ..default()
+            },
+            render_layers: MenuLayer::render_layers(),
+        }
+    }
+}
+
+/// Where we are in the tutorial that's playing (if any)
+#[derive(Resource, Debug, Default)]
+struct TutorialProgress {
+    script: Option<TutorialScript>,
+    ix: usize,
+    started: bool,
+}
+
+fn start_tutorial(
+    meta_state: Res<State<MetaState>>,
+    mut progress: ResMut<TutorialProgress>,
+    tutorial_root: Res<TutorialRoot>,
+    mut commands: Commands,
+) {
+    let (Some(tutorial_state), Some(room_state)) = (
+        meta_state.get_tutorial_state(),
+        meta_state.play_room_state(),
+    ) else {
+        return;
+    };
+    let script = tutorial_state.script();
+    commands
+        .spawn(ShipBundle::new(script.ship_pos, &room_state))
+        .set_parent(tutorial_root.eid());
+    *progress = TutorialProgress {
+        script: Some(script),
+        ..default()
+    };
+}
+
+fn spawn_step(
+    step: &TutorialStep,
+    room_state: &RoomState,
+    commands: &mut Commands,
+    tutorial_root: &TutorialRoot,
+    rng: &mut GameRng,
+) {
+    commands
+        .spawn(TutorialPromptBundle::new(step.prompt))
+        .set_parent(tutorial_root.eid());
+    for spawn in &step.spawns {
+        match spawn {
+            TutorialSpawn::Planet { pos, radius } => {
+                commands
+                    .spawn(PlanetBundle::new(
+                        "tutorial_planet",
+                        StaticTxKind::Sticky,
+                        *pos,
+                        Shape::Circle {
+                            center: Vec2::ZERO,
+                            radius: *radius,
+                        },
+                        room_state,
+                        rng.stream(RngStream::Cosmetic),
+                    ))
+                    .set_parent(tutorial_root.eid());
+            }
+            TutorialSpawn::Suicido { pos } => {
+                commands
+                    .spawn(SuicidoBundle::new(
+                        *pos,
+                        room_state,
+                        rng.stream(RngStream::Ai),
+                    ))
+                    .set_parent(tutorial_root.eid());
+            }
+        }
+    }
+}
+
+fn update_tutorial(
+    mut progress: ResMut<TutorialProgress>,
+    mut launches: EventReader<Launch>,
+    mut fires: EventReader<Fire>,
+    stuck_ships: Query<(), (With<Ship>, Added<Stuck>)>,
+    dying_suicidos: Query<&Dying, (With<Suicido>, Added<Dying>)>,
+    prompts: Query<Entity, With<TutorialPrompt>>,
+    meta_state: Res<State<MetaState>>,
+    mut bullet_time: ResMut<BulletTime>,
+    mut transitions: EventWriter<RequestTransition>,
+    mut convos: EventWriter<StartConvo>,
+    active_convo: Res<ActiveConvo>,
+    tutorial_root: Res<TutorialRoot>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let launched = launches.read().count() > 0;
+    let fired = fires.read().count() > 0;
+    let progress = &mut *progress;
+    let Some(script) = &progress.script else {
+        return;
+    };
+    let Some(step) = script.steps.get(progress.ix) else {
+        bullet_time.set_normal();
+        transitions.send(RequestTransition { to: script.then });
+        progress.script = None;
+        return;
+    };
+    if !progress.started {
+        let Some(room_state) = meta_state.play_room_state() else {
+            return;
+        };
+        spawn_step(step, &room_state, &mut commands, &tutorial_root, &mut rng);
+        if step.bullet_time {
+            bullet_time.set_slow();
+        }
+        if let TutorialGoal::Talk(path) = step.goal {
+            convos.send(StartConvo {
+                path: path.to_string(),
+            });
+        }
+        progress.started = true;
+        // Input that happened before the prompt was up doesn't count
+        return;
+    }
+    let shot_down = dying_suicidos
+        .iter()
+        .any(|dying| matches!(dying.get_cause(), DeathCause::Damage { .. }));
+    let blew_up = dying_suicidos
+        .iter()
+        .any(|dying| !matches!(dying.get_cause(), DeathCause::Damage { .. }));
+    let done = match step.goal {
+        TutorialGoal::Launch => launched,
+        TutorialGoal::Stick => !stuck_ships.is_empty(),
+        TutorialGoal::KillSuicido => shot_down,
+        TutorialGoal::Fire => fired,
+        // The conversation has opened by now (`ConvoSet` runs first)
+        TutorialGoal::Talk(_) => !active_convo.is_busy(),
+    };
+    if done {
+        for eid in &prompts {
+            commands.entity(eid).despawn_recursive();
+        }
+        if step.bullet_time {
+            bullet_time.set_normal();
+        }
+        progress.ix += 1;
+        progress.started = false;
+    } else if step.goal == TutorialGoal::KillSuicido && blew_up {
+        // Blowing itself up doesn't count, it has to be shot down. Start the step over with a fresh one.
+        for eid in &prompts {
+            commands.entity(eid).despawn_recursive();
+        }
+        progress.started = false;
+    }
+}
+
+fn stop_tutorial(mut progress: ResMut<TutorialProgress>, mut bullet_time: ResMut<BulletTime>) {
+    progress.script = None;
+    bullet_time.set_normal();
+}
+
+pub(super) struct TutorialPlugin;
+impl Plugin for TutorialPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TutorialPrompt>();
+        app.insert_resource(TutorialProgress::default());
+
+        app.add_systems(OnEnter(MetaStateKind::Tutorial), start_tutorial);
+        app.add_systems(
+            Update,
+            update_tutorial
+                .in_set(TutorialSet)
+                .after(InputSet)
+                .after(PhysicsSet)
+                .after(ConvoSet)
+                .run_if(in_state(MetaStateKind::Tutorial))
+                .run_if(in_state(TransitionState::Stable))
+                .run_if(in_state(PauseState::Unpaused)),
+        );
+        app.add_systems(OnExit(MetaStateKind::Tutorial), stop_tutorial);
+    }
+}
@@ -0,0 +1,55 @@
+//! The actual tutorials. Each `TutorialState` has exactly one script.
+
+use super::*;
+
+impl TutorialState {
+    pub fn script(&self) -> TutorialScript {
+        match self {
+            Self::Basics => basics(),
+        }
+    }
+}
+
+fn basics() -> TutorialScript {
+    TutorialScript {
+        ship_pos: Vec2::new(-120.0, 0.0),
+        steps: vec![
+            TutorialStep {
+                prompt: "Press to keep talking",
+                bullet_time: false,
+                spawns: vec![],
+                goal: TutorialGoal::Talk("convos/tutorial.convo.ron"),
+            },
+            TutorialStep {
+                prompt: "Drag to launch",
+                bullet_time: true,
+                spawns: vec![],
+                goal: TutorialGoal::Launch,
+            },
+            TutorialStep {
+                prompt: "Land on the planet",
+                bullet_time: false,
+                spawns: vec![TutorialSpawn::Planet {
+                    pos: Vec2::new(100.0, 0.0),
+                    radius: 20.0,
+                }],
+                goal: TutorialGoal::Stick,
+            },
+            TutorialStep {
+                prompt: "Right drag to fire",
+                bullet_time: false,
+                spawns: vec![],
+                goal: TutorialGoal::Fire,
+            },
+            TutorialStep {
+                prompt: "Shoot down the suicido",
+                bullet_time: false,
+                spawns: vec![TutorialSpawn::Suicido {
+                    pos: Vec2::new(-100.0, 80.0),
+                }],
+                goal: TutorialGoal::KillSuicido,
+            },
+        ],
+        then: RoomState::xth_encounter(EncounterKind::SimpOnly, 1).to_meta_state(),
+    }
+}
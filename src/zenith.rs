@@ -4,39 +4,55 @@ use consts::FRAMERATE;
 pub mod animation;
 pub mod camera;
 pub mod consts;
+pub mod convo;
+pub mod cutscene;
 pub mod debug;
 pub mod enemy;
 pub mod environment;
+pub mod health;
 pub mod input;
 pub mod layer;
 pub mod lifecycle;
 pub mod macros;
 pub mod math;
+pub mod menu;
 pub mod mirage;
 pub mod particles;
 pub mod physics;
+pub mod pool;
+pub mod rng;
 pub mod roots;
+pub mod save;
 pub mod ship;
 pub mod state;
+pub mod tutorial;
 
 pub mod prelude {
     pub use super::animation::*;
     pub use super::camera::*;
     pub use super::consts::*;
+    pub use super::convo::*;
+    pub use super::cutscene::*;
     pub use super::debug::*;
     pub use super::enemy::*;
     pub use super::environment::*;
+    pub use super::health::*;
     pub use super::input::*;
     pub use super::layer::*;
     pub use super::lifecycle::*;
     pub use super::macros::*;
     pub use super::math::*;
+    pub use super::menu::*;
     pub use super::mirage::*;
     pub use super::particles::*;
     pub use super::physics::*;
+    pub use super::pool::*;
+    pub use super::rng::*;
     pub use super::roots::*;
+    pub use super::save::*;
     pub use super::ship::*;
     pub use super::state::*;
+    pub use super::tutorial::*;
     pub use bevy::color::palettes::tailwind;
     pub use bevy::input::common_conditions::input_toggle_active;
     pub use bevy::math::VectorSpace;
@@ -46,8 +62,9 @@ pub mod prelude {
     pub use bevy_inspector_egui::quick::ResourceInspectorPlugin;
     pub use core::f32;
     pub use f32::consts::PI;
-    pub use rand::thread_rng;
-    pub use rand::Rng;
+    pub use rand::rngs::StdRng;
+    pub use rand::{Rng, SeedableRng};
+    pub use serde::{Deserialize, Serialize};
     pub use std::collections::VecDeque;
     pub use std::time::Duration;
 }
@@ -62,15 +79,23 @@ pub fn launch_app(mut app: App) {
     ));
     app.add_plugins(animation::AnimationPlugin);
     app.add_plugins(camera::CameraPlugin);
+    app.add_plugins(convo::ConvoPlugin);
+    app.add_plugins(cutscene::CutscenePlugin);
     app.add_plugins(enemy::EnemyPlugin);
     app.add_plugins(environment::EnvironmentPlugin);
+    app.add_plugins(health::HealthPlugin);
     app.add_plugins(input::CommonInputPlugin);
     app.add_plugins(lifecycle::LifecyclePlugin);
+    app.add_plugins(menu::MenuPlugin);
     app.add_plugins(mirage::MiragePlugin);
     app.add_plugins(particles::ParticlesPlugin);
     app.add_plugins(physics::PhysicsPlugin);
+    app.add_plugins(rng::RngPlugin);
     app.add_plugins(roots::RootPlugin);
     app.add_plugins(state::StatePlugin);
+    app.add_plugins(save::SavePlugin);
+    app.add_plugins(ship::ShipPlugin);
+    app.add_plugins(tutorial::TutorialPlugin);
     app.run();
 }
 
@@ -91,5 +116,6 @@ fn main() {
             ..default()
         }), // .set(ImagePlugin::default_nearest()),
     );
+    app.add_plugins(TouchInputPlugin);
     launch_app(app);
 }